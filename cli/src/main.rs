@@ -1,10 +1,111 @@
-use recurrence::RecurrenceRelation;
+use recurrence::{
+    ascii_plot, EvaluationError, ExplanationFormat, IteratedMap, NonlinearRecurrence,
+    RecurrenceRelation, Stability, LYAPUNOV_TOLERANCE,
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
 struct Cli {
     /// The string representing the recurrence relation  to look for
     recurrence_relation_string: String,
+
+    /// Explain each step of the solution as text, markdown or latex
+    #[structopt(long, parse(try_from_str = parse_explanation_format))]
+    explain: Option<ExplanationFormat>,
+}
+
+fn parse_explanation_format(s: &str) -> Result<ExplanationFormat, String> {
+    match s {
+        "text" => Ok(ExplanationFormat::Text),
+        "markdown" => Ok(ExplanationFormat::Markdown),
+        "latex" => Ok(ExplanationFormat::Latex),
+        _ => Err(format!(
+            "unknown format {}, expected text, markdown or latex",
+            s
+        )),
+    }
+}
+
+fn print_nonlinear(mut recurrence: NonlinearRecurrence) {
+    let terms = match recurrence.terms(60) {
+        Ok(terms) => terms,
+        Err(EvaluationError::IndexOutOfRange { n, index }) => {
+            eprintln!(
+                "term {} refers to the term at {}, which is not before it",
+                n, index
+            );
+            return;
+        }
+        Err(EvaluationError::UnknownParameter(name)) => {
+            eprintln!(
+                "the parameter {} has no value, give it one with {} = ...",
+                name, name
+            );
+            return;
+        }
+        Err(EvaluationError::StepLimitExceeded) => {
+            eprintln!("too many terms to compute");
+            return;
+        }
+    };
+    println!("This recurrence relation is evaluated term by term");
+    println!(
+        "The first 10 terms of this recurrence relation are: {:?}",
+        &terms[..10]
+    );
+    println!("The first {} terms:", terms.len());
+    print!("{}", ascii_plot(&terms, 15));
+    if let Some(map) = recurrence.map() {
+        print_dynamics(&map, &terms);
+    }
+}
+
+fn print_dynamics(map: &IteratedMap, terms: &[f64]) {
+    // look for fixed points around the terms, widened by half their spread
+    let finite = terms.iter().copied().filter(|x| x.is_finite());
+    let max = finite.clone().fold(f64::NEG_INFINITY, f64::max);
+    let min = finite.fold(f64::INFINITY, f64::min);
+    if min > max {
+        return;
+    }
+    let margin = ((max - min) / 2.0).max(1.0);
+    if let Ok(fixed_points) = map.fixed_points(min - margin, max + margin) {
+        let descriptions: Vec<String> = fixed_points
+            .iter()
+            .map(|fixed_point| {
+                let stability = match fixed_point.stability() {
+                    Stability::Stable => "stable",
+                    Stability::MarginallyStable => "neutral",
+                    Stability::Unstable => "unstable",
+                };
+                // round away the error left by the root finding, such as 1e-34 for 0
+                let x = (fixed_point.points()[0] * 1e9).round() / 1e9 + 0.0;
+                format!("{} ({})", x, stability)
+            })
+            .collect();
+        if !descriptions.is_empty() {
+            println!(
+                "The fixed points near these terms are: {}",
+                descriptions.join(", ")
+            );
+        }
+    }
+    if let Ok(exponent) = map.lyapunov_exponent(terms[0], 1000, 10000) {
+        if exponent.is_finite() {
+            // exponents within the error of the estimate are neutral
+            let behaviour = if exponent > LYAPUNOV_TOLERANCE {
+                "chaotic"
+            } else if exponent < -LYAPUNOV_TOLERANCE {
+                "not chaotic"
+            } else {
+                "neutral"
+            };
+            println!(
+                "The Lyapunov exponent of this orbit is {:.4}, so it is {}",
+                exponent, behaviour
+            );
+        }
+    }
 }
 
 fn main() {
@@ -12,17 +113,32 @@ fn main() {
     let recurrence_relation: RecurrenceRelation =
         match args.recurrence_relation_string.trim().parse() {
             Ok(recurrence_relation) => recurrence_relation,
-            Err(_) => {
-                eprintln!("invalid recurrence relation");
+            Err(error) => {
+                // relations which are not linear can still be evaluated term by term
+                // if that fails too, the linear error is reported, as most relations are linear
+                match args.recurrence_relation_string.trim().parse() {
+                    Ok(nonlinear) => print_nonlinear(nonlinear),
+                    Err(_) => eprintln!("invalid recurrence relation: {:?}", error),
+                }
                 return;
             }
         };
 
+    if let Some(format) = args.explain {
+        println!("{}", recurrence_relation.explain().render(format));
+        return;
+    }
+
     let recurrence_solution = recurrence_relation.solve();
     println!(
         "The solution to this recurrence relation is: {}",
         recurrence_solution
     );
+    if let Some(exact_solution) = recurrence_relation.solve_exact() {
+        println!("The exact solution is: {}", exact_solution);
+    } else if let Some(identified_solution) = recurrence_solution.identify() {
+        println!("The solution appears to be: {}", identified_solution);
+    }
     println!(
         "The first 10 terms of this recurrence relation are: {:?}",
         recurrence_relation.get_terms(10)
@@ -0,0 +1,784 @@
+use crate::rational_polynomial::{gcd, rational_to_f64, Rational, RationalPolynomial};
+use num_complex::Complex;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Signed, Zero};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// an exact algebraic number written with rationals, radicals and field operations
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Algebraic {
+    Rational(Rational),
+    /// rational + coefficient * √radicand, the radicand is a squarefree integer which may be negative
+    Surd {
+        rational: Rational,
+        coefficient: Rational,
+        radicand: i128,
+    },
+    Sum(Vec<Algebraic>),
+    Product(Vec<Algebraic>),
+    Quotient(Box<Algebraic>, Box<Algebraic>),
+    Power(Box<Algebraic>, u32),
+    /// the k-th root, real when the radicand is real and principal otherwise
+    Root(Box<Algebraic>, u32),
+    Neg(Box<Algebraic>),
+}
+
+// splits n into k^2 * m with m squarefree, keeping the sign of n in m
+fn square_free_part(n: i128) -> (i128, i128) {
+    let sign = if n < 0 { -1 } else { 1 };
+    let mut m = n.abs();
+    let mut k = 1;
+    let mut p = 2;
+    while p * p <= m {
+        while m % (p * p) == 0 {
+            m /= p * p;
+            k *= p;
+        }
+        p += 1;
+    }
+    (k, sign * m)
+}
+
+// returns the exact integer k-th root of n if there is one
+fn integer_root(n: i128, k: u32) -> Option<i128> {
+    if n < 0 {
+        return if k % 2 == 1 {
+            integer_root(-n, k).map(|r| -r)
+        } else {
+            None
+        };
+    }
+    let guess = (n as f64).powf(1.0 / k as f64).round() as i128;
+    (guess.max(1) - 1..=guess + 1).find(|r| r.checked_pow(k) == Some(n))
+}
+
+// the rational part and the coefficient of the radical of a surd
+type SurdParts = (Rational, Rational);
+
+impl Algebraic {
+    pub fn rational(x: Rational) -> Algebraic {
+        Algebraic::Rational(x)
+    }
+
+    pub fn integer(x: i128) -> Algebraic {
+        Algebraic::Rational(Rational::from(x))
+    }
+
+    /// returns rational + coefficient * √radicand in normal form
+    pub fn surd(rational: Rational, coefficient: Rational, radicand: i128) -> Algebraic {
+        Algebraic::checked_surd(rational, coefficient, radicand).expect("surd overflowed")
+    }
+
+    // returns the surd as surd does, or None if moving the square out of the radicand overflows
+    fn checked_surd(
+        rational: Rational,
+        coefficient: Rational,
+        radicand: i128,
+    ) -> Option<Algebraic> {
+        let (k, m) = square_free_part(radicand);
+        let coefficient = coefficient.checked_mul(&Rational::from(k))?;
+        let res = if coefficient.is_zero() || m == 0 {
+            Algebraic::Rational(rational)
+        } else if m == 1 {
+            Algebraic::Rational(rational.checked_add(&coefficient)?)
+        } else {
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                radicand: m,
+            }
+        };
+        Some(res)
+    }
+
+    /// returns the square root of a rational as a surd
+    pub fn sqrt_rational(x: Rational) -> Algebraic {
+        // √(p/q) = √(pq)/q
+        let denominator = *x.denom();
+        Algebraic::surd(
+            Rational::zero(),
+            Rational::new(1, denominator),
+            x.numer() * denominator,
+        )
+    }
+
+    /// returns the k-th root of a number, simplified when the number is rational
+    pub fn root(x: Algebraic, k: u32) -> Algebraic {
+        if k == 1 {
+            return x;
+        }
+        if let Algebraic::Rational(r) = x {
+            if k == 2 {
+                return Algebraic::sqrt_rational(r);
+            }
+            if let (Some(p), Some(q)) = (integer_root(*r.numer(), k), integer_root(*r.denom(), k)) {
+                return Algebraic::Rational(Rational::new(p, q));
+            }
+        }
+        Algebraic::Root(Box::new(x), k)
+    }
+
+    pub fn pow(&self, k: u32) -> Algebraic {
+        self.checked_pow(k).expect("algebraic power overflowed")
+    }
+
+    /// returns the k-th power, or None if multiplying out a rational or a surd overflows
+    pub fn checked_pow(&self, k: u32) -> Option<Algebraic> {
+        let res = match self {
+            Algebraic::Rational(_) | Algebraic::Surd { .. } => {
+                let mut res = Algebraic::integer(1);
+                for _ in 0..k {
+                    res = res.checked_mul(self)?;
+                }
+                res
+            }
+            _ if k == 0 => Algebraic::integer(1),
+            _ if k == 1 => self.clone(),
+            _ => Algebraic::Power(Box::new(self.clone()), k),
+        };
+        Some(res)
+    }
+
+    /// returns the sum, or None if the exact arithmetic overflows
+    pub fn checked_add(&self, other: &Algebraic) -> Option<Algebraic> {
+        if self.is_zero() {
+            return Some(other.clone());
+        }
+        if other.is_zero() {
+            return Some(self.clone());
+        }
+        if let Some(((a1, b1), (a2, b2), d)) = self.common_surds(other) {
+            return Algebraic::checked_surd(a1.checked_add(&a2)?, b1.checked_add(&b2)?, d);
+        }
+        let mut terms = Vec::new();
+        for x in [self, other] {
+            match x {
+                Algebraic::Sum(inner) => terms.extend(inner.iter().cloned()),
+                x => terms.push(x.clone()),
+            }
+        }
+        Some(Algebraic::Sum(terms))
+    }
+
+    /// returns the difference, or None if the exact arithmetic overflows
+    pub fn checked_sub(&self, other: &Algebraic) -> Option<Algebraic> {
+        self.checked_add(&-other.clone())
+    }
+
+    /// returns the product, or None if the exact arithmetic overflows
+    pub fn checked_mul(&self, other: &Algebraic) -> Option<Algebraic> {
+        if self.is_zero() || other.is_zero() {
+            return Some(Algebraic::integer(0));
+        }
+        if self.is_one() {
+            return Some(other.clone());
+        }
+        if other.is_one() {
+            return Some(self.clone());
+        }
+        if let Some(((a1, b1), (a2, b2), d)) = self.common_surds(other) {
+            let radicand = Rational::from(d);
+            return Algebraic::checked_surd(
+                a1.checked_mul(&a2)?
+                    .checked_add(&b1.checked_mul(&b2)?.checked_mul(&radicand)?)?,
+                a1.checked_mul(&b2)?.checked_add(&a2.checked_mul(&b1)?)?,
+                d,
+            );
+        }
+        // keep a single rational factor at the front of the product
+        let mut scale = Rational::one();
+        let mut factors = Vec::new();
+        for x in [self, other] {
+            match x {
+                Algebraic::Product(inner) => {
+                    for y in inner {
+                        match y {
+                            Algebraic::Rational(r) => scale = scale.checked_mul(r)?,
+                            y => factors.push(y.clone()),
+                        }
+                    }
+                }
+                Algebraic::Rational(r) => scale = scale.checked_mul(r)?,
+                Algebraic::Neg(y) => {
+                    scale = -scale;
+                    factors.push((**y).clone());
+                }
+                x => factors.push(x.clone()),
+            }
+        }
+        if !scale.is_one() {
+            factors.insert(0, Algebraic::Rational(scale));
+        }
+        if factors.len() == 1 {
+            Some(factors.remove(0))
+        } else {
+            Some(Algebraic::Product(factors))
+        }
+    }
+
+    /// returns the quotient, or None if the divisor is zero or the exact arithmetic overflows
+    pub fn checked_div(&self, other: &Algebraic) -> Option<Algebraic> {
+        if other.is_zero() {
+            return None;
+        }
+        if let Some(((a1, b1), (a2, b2), d)) = self.common_surds(other) {
+            // multiply through by the conjugate of the denominator
+            let radicand = Rational::from(d);
+            let norm = a2
+                .checked_mul(&a2)?
+                .checked_sub(&b2.checked_mul(&b2)?.checked_mul(&radicand)?)?;
+            return Algebraic::checked_surd(
+                a1.checked_mul(&a2)?
+                    .checked_sub(&b1.checked_mul(&b2)?.checked_mul(&radicand)?)?
+                    .checked_div(&norm)?,
+                b1.checked_mul(&a2)?
+                    .checked_sub(&a1.checked_mul(&b2)?)?
+                    .checked_div(&norm)?,
+                d,
+            );
+        }
+        if let Algebraic::Rational(r) = other {
+            return Algebraic::Rational(Rational::one().checked_div(r)?).checked_mul(self);
+        }
+        Some(Algebraic::Quotient(
+            Box::new(self.clone()),
+            Box::new(other.clone()),
+        ))
+    }
+
+    /// returns the number as a rational if it is written as one
+    pub fn as_rational(&self) -> Option<Rational> {
+        match self {
+            Algebraic::Rational(r) => Some(*r),
+            _ => None,
+        }
+    }
+
+    // returns the number as rational + coefficient * √radicand, with radicand 1 for rationals
+    fn as_surd(&self) -> Option<(Rational, Rational, i128)> {
+        match self {
+            Algebraic::Rational(r) => Some((*r, Rational::zero(), 1)),
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                radicand,
+            } => Some((*rational, *coefficient, *radicand)),
+            _ => None,
+        }
+    }
+
+    // returns both numbers as surds over a shared radicand if they have one
+    fn common_surds(&self, other: &Algebraic) -> Option<(SurdParts, SurdParts, i128)> {
+        let (a1, b1, d1) = self.as_surd()?;
+        let (a2, b2, d2) = other.as_surd()?;
+        if d1 == d2 || d2 == 1 {
+            Some(((a1, b1), (a2, b2), d1))
+        } else if d1 == 1 {
+            Some(((a1, b1), (a2, b2), d2))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        matches!(self, Algebraic::Rational(r) if r.is_zero())
+    }
+
+    pub fn is_one(&self) -> bool {
+        matches!(self, Algebraic::Rational(r) if r.is_one())
+    }
+
+    // whether the number is best written as a minus sign followed by its negation
+    pub(crate) fn is_negative_form(&self) -> bool {
+        match self {
+            Algebraic::Rational(r) => r.is_negative(),
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                ..
+            } => rational.is_zero() && coefficient.is_negative(),
+            Algebraic::Product(factors) => factors.first().is_some_and(|f| f.is_negative_form()),
+            Algebraic::Quotient(numerator, _) => numerator.is_negative_form(),
+            Algebraic::Neg(_) => true,
+            _ => false,
+        }
+    }
+
+    /// returns the numeric value of the number
+    pub fn evaluate(&self) -> Complex<f64> {
+        match self {
+            Algebraic::Rational(r) => Complex::new(rational_to_f64(r), 0.0),
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                radicand,
+            } => {
+                let root = if *radicand < 0 {
+                    Complex::new(0.0, (-*radicand as f64).sqrt())
+                } else {
+                    Complex::new((*radicand as f64).sqrt(), 0.0)
+                };
+                Complex::new(rational_to_f64(rational), 0.0) + root * rational_to_f64(coefficient)
+            }
+            Algebraic::Sum(terms) => terms.iter().map(|t| t.evaluate()).sum(),
+            Algebraic::Product(factors) => factors.iter().map(|f| f.evaluate()).product(),
+            Algebraic::Quotient(numerator, denominator) => {
+                numerator.evaluate() / denominator.evaluate()
+            }
+            Algebraic::Power(base, k) => base.evaluate().powu(*k),
+            Algebraic::Root(radicand, k) => {
+                let value = radicand.evaluate();
+                if value.im.abs() <= 1e-12 * value.re.abs().max(1.0) {
+                    if value.re >= 0.0 {
+                        Complex::new(value.re.powf(1.0 / *k as f64), 0.0)
+                    } else if k % 2 == 1 {
+                        Complex::new(-(-value.re).powf(1.0 / *k as f64), 0.0)
+                    } else {
+                        Complex::from_polar(
+                            (-value.re).powf(1.0 / *k as f64),
+                            std::f64::consts::PI / *k as f64,
+                        )
+                    }
+                } else {
+                    value.powf(1.0 / *k as f64)
+                }
+            }
+            Algebraic::Neg(x) => -x.evaluate(),
+        }
+    }
+
+    /// returns the value of the polynomial at this number, simplified where possible
+    /// returns None if the exact arithmetic overflows
+    pub fn substitute_into(&self, polynomial: &RationalPolynomial) -> Option<Algebraic> {
+        let mut res = Algebraic::integer(0);
+        for (power, coefficient) in polynomial.coefficients().iter().enumerate() {
+            if !coefficient.is_zero() {
+                let term = Algebraic::Rational(*coefficient)
+                    .checked_mul(&self.checked_pow(power as u32)?)?;
+                res = res.checked_add(&term)?;
+            }
+        }
+        Some(res)
+    }
+
+    // whether the number can be written next to others without parentheses
+    pub(crate) fn is_atomic(&self) -> bool {
+        match self {
+            Algebraic::Rational(r) => r.is_integer() && !r.is_negative(),
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                ..
+            } => rational.is_zero() && coefficient.is_integer() && coefficient.is_positive(),
+            Algebraic::Root(..) | Algebraic::Power(..) => true,
+            _ => false,
+        }
+    }
+
+    // formats the number, wrapped in parentheses unless it is atomic
+    pub(crate) fn to_atomic_string(&self) -> String {
+        if self.is_atomic() {
+            self.to_string()
+        } else {
+            format!("({})", self)
+        }
+    }
+}
+
+fn format_rational(r: &Rational) -> String {
+    if r.is_integer() {
+        format!("{}", r.numer())
+    } else {
+        format!("{}/{}", r.numer(), r.denom())
+    }
+}
+
+fn format_radical(radicand: i128) -> String {
+    match radicand {
+        -1 => "i".to_string(),
+        d if d < 0 => format!("i√{}", -d),
+        d => format!("√{}", d),
+    }
+}
+
+impl fmt::Display for Algebraic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Algebraic::Rational(r) => write!(f, "{}", format_rational(r)),
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                radicand,
+            } => {
+                let radical = format_radical(*radicand);
+                if rational.is_zero() {
+                    let sign = if coefficient.is_negative() { "-" } else { "" };
+                    let numerator = coefficient.numer().abs();
+                    let denominator = *coefficient.denom();
+                    if numerator == 1 && denominator == *radicand {
+                        // p/d √d is written as p/√d
+                        return write!(f, "{}1/{}", sign, radical);
+                    }
+                    let scaled = if numerator == 1 {
+                        radical
+                    } else {
+                        format!("{}{}", numerator, radical)
+                    };
+                    if denominator == 1 {
+                        write!(f, "{}{}", sign, scaled)
+                    } else {
+                        write!(f, "{}{}/{}", sign, scaled, denominator)
+                    }
+                } else {
+                    let denominator = rational.denom()
+                        / gcd(*rational.denom(), *coefficient.denom())
+                        * coefficient.denom();
+                    let a = (rational * Rational::from(denominator)).to_integer();
+                    let b = (coefficient * Rational::from(denominator)).to_integer();
+                    let sign = if b < 0 { "-" } else { "+" };
+                    let scaled = if b.abs() == 1 {
+                        radical
+                    } else {
+                        format!("{}{}", b.abs(), radical)
+                    };
+                    if denominator == 1 {
+                        write!(f, "{}{}{}", a, sign, scaled)
+                    } else {
+                        write!(f, "({}{}{})/{}", a, sign, scaled, denominator)
+                    }
+                }
+            }
+            Algebraic::Sum(terms) => {
+                for (i, term) in terms.iter().enumerate() {
+                    if i == 0 {
+                        write!(f, "{}", term)?;
+                    } else if term.is_negative_form() {
+                        write!(f, " - {}", -term.clone())?;
+                    } else {
+                        write!(f, " + {}", term)?;
+                    }
+                }
+                Ok(())
+            }
+            Algebraic::Product(factors) => {
+                let factors: Vec<String> = factors.iter().map(|x| x.to_atomic_string()).collect();
+                write!(f, "{}", factors.join("·"))
+            }
+            Algebraic::Quotient(numerator, denominator) => write!(
+                f,
+                "{}/{}",
+                numerator.to_atomic_string(),
+                denominator.to_atomic_string()
+            ),
+            Algebraic::Power(base, k) => write!(f, "{}^{}", base.to_atomic_string(), k),
+            Algebraic::Root(radicand, k) => match k {
+                2 => write!(f, "√{}", radicand.to_atomic_string()),
+                3 => write!(f, "∛{}", radicand.to_atomic_string()),
+                4 => write!(f, "∜{}", radicand.to_atomic_string()),
+                _ => write!(f, "{}^(1/{})", radicand.to_atomic_string(), k),
+            },
+            Algebraic::Neg(x) => write!(f, "-{}", x.to_atomic_string()),
+        }
+    }
+}
+
+// the operators panic on overflow like those of the integers, use the checked methods to get None
+impl Add for Algebraic {
+    type Output = Algebraic;
+
+    fn add(self, other: Algebraic) -> Algebraic {
+        self.checked_add(&other)
+            .expect("algebraic addition overflowed")
+    }
+}
+
+impl Neg for Algebraic {
+    type Output = Algebraic;
+
+    fn neg(self) -> Algebraic {
+        match self {
+            Algebraic::Rational(r) => Algebraic::Rational(-r),
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                radicand,
+            } => Algebraic::Surd {
+                rational: -rational,
+                coefficient: -coefficient,
+                radicand,
+            },
+            Algebraic::Neg(x) => *x,
+            Algebraic::Quotient(numerator, denominator) => {
+                Algebraic::Quotient(Box::new(-*numerator), denominator)
+            }
+            Algebraic::Sum(terms) => Algebraic::Sum(terms.into_iter().map(|t| -t).collect()),
+            Algebraic::Product(mut factors) => {
+                factors[0] = -factors[0].clone();
+                if factors[0].is_one() {
+                    factors.remove(0);
+                    if factors.len() == 1 {
+                        return factors.remove(0);
+                    }
+                }
+                Algebraic::Product(factors)
+            }
+            x => Algebraic::Neg(Box::new(x)),
+        }
+    }
+}
+
+impl Sub for Algebraic {
+    type Output = Algebraic;
+
+    fn sub(self, other: Algebraic) -> Algebraic {
+        self + (-other)
+    }
+}
+
+impl Mul for Algebraic {
+    type Output = Algebraic;
+
+    fn mul(self, other: Algebraic) -> Algebraic {
+        self.checked_mul(&other)
+            .expect("algebraic multiplication overflowed")
+    }
+}
+
+impl Div for Algebraic {
+    type Output = Algebraic;
+
+    fn div(self, other: Algebraic) -> Algebraic {
+        if other.is_zero() {
+            panic!("division by zero")
+        }
+        self.checked_div(&other)
+            .expect("algebraic division overflowed")
+    }
+}
+
+/// returns the roots of a monic irreducible polynomial of degree at most four in radicals
+/// returns None for higher degrees or if the exact arithmetic overflows
+pub fn roots_in_radicals(polynomial: &RationalPolynomial) -> Option<Vec<Algebraic>> {
+    let monic = polynomial.monic()?;
+    let c = monic.coefficients();
+    match monic.degree() {
+        1 => Some(vec![Algebraic::Rational(-c[0])]),
+        2 => quadratic_roots(c[1], c[0]),
+        3 => cubic_roots(c[2], c[1], c[0]),
+        4 => quartic_roots(c[3], c[2], c[1], c[0]),
+        _ => None,
+    }
+}
+
+// largest radicand simplified to a squarefree one, since that takes trial division up to its
+// square root
+const MAX_RADICAND: i128 = 1_000_000_000_000;
+
+// returns √x as sqrt_rational does, or None if the radicand overflows or is too large to simplify
+fn checked_sqrt_rational(x: Rational) -> Option<Algebraic> {
+    let radicand = x.numer().checked_mul(x.denom())?;
+    if radicand.abs() > MAX_RADICAND {
+        return None;
+    }
+    Some(Algebraic::sqrt_rational(x))
+}
+
+// returns the k-th root as root does, checking square roots of rationals as above
+fn checked_root(x: Algebraic, k: u32) -> Option<Algebraic> {
+    match x {
+        Algebraic::Rational(r) if k == 2 => checked_sqrt_rational(r),
+        x => Some(Algebraic::root(x, k)),
+    }
+}
+
+// returns a/b, or None if it overflows
+fn quotient(a: Rational, b: i128) -> Option<Rational> {
+    a.checked_div(&Rational::from(b))
+}
+
+// roots of x^2 + bx + c
+fn quadratic_roots(b: Rational, c: Rational) -> Option<Vec<Algebraic>> {
+    let half = Rational::new(1, 2);
+    let discriminant = checked_sqrt_rational(
+        b.checked_mul(&b)?
+            .checked_sub(&Rational::from(4).checked_mul(&c)?)?,
+    )?;
+    let shift = Algebraic::Rational(quotient(-b, 2)?);
+    let half_discriminant = Algebraic::Rational(half).checked_mul(&discriminant)?;
+    Some(vec![
+        shift.checked_add(&half_discriminant)?,
+        shift.checked_sub(&half_discriminant)?,
+    ])
+}
+
+// roots of t^3 + pt + q with cardano's formula
+fn depressed_cubic_roots(p: Rational, q: Rational) -> Option<Vec<Algebraic>> {
+    let half = Rational::new(1, 2);
+    let discriminant = checked_sqrt_rational(
+        quotient(q.checked_mul(&q)?, 4)?
+            .checked_add(&quotient(p.checked_mul(&p)?.checked_mul(&p)?, 27)?)?,
+    )?;
+    let minus_half_q = quotient(-q, 2)?;
+    let mut u = Algebraic::Rational(minus_half_q).checked_add(&discriminant)?;
+    if u.is_zero() {
+        u = Algebraic::Rational(minus_half_q).checked_sub(&discriminant)?;
+    }
+    let cube_root = Algebraic::root(u, 3);
+    let omega = Algebraic::surd(-half, half, -3);
+    let third_p = quotient(p, 3)?;
+
+    (0..3)
+        .map(|k| {
+            let w = omega.checked_pow(k)?.checked_mul(&cube_root)?;
+            if p.is_zero() {
+                Some(w)
+            } else {
+                w.checked_sub(&Algebraic::Rational(third_p).checked_div(&w)?)
+            }
+        })
+        .collect()
+}
+
+// roots of x^3 + bx^2 + cx + d
+fn cubic_roots(b: Rational, c: Rational, d: Rational) -> Option<Vec<Algebraic>> {
+    // substitute x = t - b/3
+    let b2 = b.checked_mul(&b)?;
+    let p = c.checked_sub(&quotient(b2, 3)?)?;
+    let q = quotient(Rational::from(2).checked_mul(&b2)?.checked_mul(&b)?, 27)?
+        .checked_sub(&quotient(b.checked_mul(&c)?, 3)?)?
+        .checked_add(&d)?;
+    let shift = Algebraic::Rational(quotient(-b, 3)?);
+    depressed_cubic_roots(p, q)?
+        .iter()
+        .map(|t| t.checked_add(&shift))
+        .collect()
+}
+
+// roots of x^4 + bx^3 + cx^2 + dx + e with ferrari's method
+fn quartic_roots(b: Rational, c: Rational, d: Rational, e: Rational) -> Option<Vec<Algebraic>> {
+    // substitute x = y - b/4 to get y^4 + py^2 + qy + r
+    let b2 = b.checked_mul(&b)?;
+    let p = c.checked_sub(&Rational::new(3, 8).checked_mul(&b2)?)?;
+    let q = d
+        .checked_sub(&quotient(b.checked_mul(&c)?, 2)?)?
+        .checked_add(&quotient(b2.checked_mul(&b)?, 8)?)?;
+    let r = e
+        .checked_sub(&quotient(b.checked_mul(&d)?, 4)?)?
+        .checked_add(&quotient(b2.checked_mul(&c)?, 16)?)?
+        .checked_sub(&Rational::new(3, 256).checked_mul(&b2)?.checked_mul(&b2)?)?;
+    let shift = Algebraic::Rational(quotient(-b, 4)?);
+    let half = Algebraic::Rational(Rational::new(1, 2));
+
+    let mut roots = Vec::new();
+    if q.is_zero() {
+        // biquadratic, solve for y^2 first
+        for z in quadratic_roots(p, r)? {
+            let y = checked_root(z, 2)?;
+            roots.push(y.checked_add(&shift)?);
+            roots.push(shift.checked_sub(&y)?);
+        }
+        return Some(roots);
+    }
+
+    // a nonzero root of the resolvent cubic m^3 + pm^2 + (p^2/4 - r)m - q^2/8
+    let constant = -quotient(q.checked_mul(&q)?, 8)?;
+    let linear = quotient(p.checked_mul(&p)?, 4)?.checked_sub(&r)?;
+    let resolvent = RationalPolynomial::new(vec![constant, linear, p, Rational::one()]);
+    let m = match resolvent.rational_roots()?.first() {
+        Some(m) => Algebraic::Rational(*m),
+        None => cubic_roots(p, linear, constant)?.remove(0),
+    };
+
+    let two = Algebraic::integer(2);
+    let two_p = Algebraic::Rational(Rational::from(2).checked_mul(&p)?);
+    let two_q = Algebraic::Rational(Rational::from(2).checked_mul(&q)?);
+    let two_m = two.checked_mul(&m)?;
+    let sqrt_2m = checked_root(two_m.clone(), 2)?;
+    for s1 in &[1, -1] {
+        let s1 = Algebraic::integer(*s1);
+        // √2 q / √m = 2q / √(2m)
+        let inner = -two_p
+            .checked_add(&two_m)?
+            .checked_add(&s1.checked_mul(&two_q)?.checked_div(&sqrt_2m)?)?;
+        let sqrt_inner = checked_root(inner, 2)?;
+        for s2 in &[1, -1] {
+            let sum = s1
+                .checked_mul(&sqrt_2m)?
+                .checked_add(&Algebraic::integer(*s2).checked_mul(&sqrt_inner)?)?;
+            roots.push(half.checked_mul(&sum)?.checked_add(&shift)?);
+        }
+    }
+    Some(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(polynomial: &RationalPolynomial) {
+        let roots = roots_in_radicals(polynomial).unwrap();
+        assert_eq!(roots.len(), polynomial.degree());
+        for root in roots {
+            let mut value = Complex::new(0.0, 0.0);
+            for coefficient in polynomial.coefficients().iter().rev() {
+                value = value * root.evaluate() + rational_to_f64(coefficient);
+            }
+            assert!(value.norm() < 1e-9, "{} is not a root", root);
+        }
+    }
+
+    #[test]
+    fn test_surd_arithmetic() {
+        let phi = Algebraic::surd(Rational::new(1, 2), Rational::new(1, 2), 5);
+        assert_eq!(phi.to_string(), "(1+√5)/2");
+        assert_eq!(
+            phi.clone() * phi.clone(),
+            phi.clone() + Algebraic::integer(1)
+        );
+        assert_eq!(
+            Algebraic::integer(1) / Algebraic::sqrt_rational(Rational::from(5)),
+            Algebraic::surd(Rational::zero(), Rational::new(1, 5), 5)
+        );
+        assert_eq!(
+            Algebraic::sqrt_rational(Rational::new(-12, 1)).to_string(),
+            "2i√3"
+        );
+        assert_eq!(
+            Algebraic::surd(Rational::zero(), Rational::new(-1, 5), 5).to_string(),
+            "-1/√5"
+        );
+
+        let large = Algebraic::surd(Rational::from(1i128 << 100), Rational::one(), 2);
+        assert_eq!(large.checked_mul(&large), None);
+        assert_eq!(
+            large.checked_add(&large),
+            Some(large.clone() * Algebraic::integer(2))
+        );
+        assert_eq!(phi.checked_div(&Algebraic::integer(0)), None);
+        let square = RationalPolynomial::from_integers(&[0, 0, 1]);
+        assert_eq!(large.substitute_into(&square), None);
+        assert_eq!(
+            phi.substitute_into(&square),
+            Some(phi.clone() + Algebraic::integer(1))
+        );
+    }
+
+    #[test]
+    fn test_roots_in_radicals() {
+        let golden = RationalPolynomial::from_integers(&[-1, -1, 1]);
+        let roots = roots_in_radicals(&golden).unwrap();
+        assert_eq!(roots[0].to_string(), "(1+√5)/2");
+        assert_eq!(roots[1].to_string(), "(1-√5)/2");
+
+        let cube = RationalPolynomial::from_integers(&[-2, 0, 0, 1]);
+        assert_eq!(roots_in_radicals(&cube).unwrap()[0].to_string(), "∛2");
+        assert_roots(&cube);
+
+        assert_roots(&RationalPolynomial::from_integers(&[-1, -1, 0, 1]));
+        assert_roots(&RationalPolynomial::from_integers(&[1, -3, 0, 1]));
+        assert_roots(&RationalPolynomial::from_integers(&[1, 0, -10, 0, 1]));
+        assert_roots(&RationalPolynomial::from_integers(&[-1, -1, 0, 0, 1]));
+        assert_roots(&RationalPolynomial::from_integers(&[3, 1, 2, -1, 1]));
+    }
+}
@@ -0,0 +1,319 @@
+use crate::format::number;
+use crate::recurrence_relation::RecurrenceRelation;
+use crate::recurrence_solution::RecurrenceSolution;
+use nalgebra::DMatrix;
+use num_complex::Complex;
+use std::fmt;
+
+// relative tolerance for roots of equal modulus and for coefficients treated as zero
+const TOLERANCE: f64 = 1e-9;
+
+/// how the size of the terms of a sequence behaves as n grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Growth {
+    /// the terms grow without bound
+    Grows,
+    /// the terms stay bounded without tending to zero
+    Bounded,
+    /// the terms tend to zero
+    Decays,
+    /// the terms are eventually all zero
+    Zero,
+}
+
+/// the leading behaviour of a sequence, the sum of C·n^k·r^n over its dominant roots r
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Asymptotics {
+    dominant_roots: Vec<Complex<f64>>,
+    coefficients: Vec<Complex<f64>>,
+    power: usize,
+}
+
+impl Asymptotics {
+    // finds the dominant roots of the terms, given as roots with the coefficients of n^i root^n
+    fn from_terms(terms: &[(Complex<f64>, Vec<Complex<f64>>)]) -> Asymptotics {
+        let scale = terms
+            .iter()
+            .flat_map(|(_, coefficients)| coefficients.iter().map(|c| c.norm()))
+            .fold(0.0, f64::max);
+        let is_negligible = |c: &Complex<f64>| c.norm() <= TOLERANCE * scale;
+        // each root with the highest power of n that has a coefficient, and that coefficient
+        let leading: Vec<(Complex<f64>, usize, Complex<f64>)> = terms
+            .iter()
+            .filter(|(root, _)| root.norm() > 0.0)
+            .filter_map(|(root, coefficients)| {
+                let power = coefficients.iter().rposition(|c| !is_negligible(c))?;
+                Some((*root, power, coefficients[power]))
+            })
+            .collect();
+
+        let modulus = leading
+            .iter()
+            .map(|(root, ..)| root.norm())
+            .fold(0.0, f64::max);
+        let dominant: Vec<&(Complex<f64>, usize, Complex<f64>)> = leading
+            .iter()
+            .filter(|(root, ..)| root.norm() >= modulus * (1.0 - TOLERANCE))
+            .collect();
+        let power = dominant
+            .iter()
+            .map(|(_, power, _)| *power)
+            .max()
+            .unwrap_or(0);
+        let dominant: Vec<_> = dominant
+            .into_iter()
+            .filter(|(_, p, _)| *p == power)
+            .collect();
+
+        Asymptotics {
+            dominant_roots: dominant.iter().map(|(root, ..)| *root).collect(),
+            coefficients: dominant.iter().map(|(.., c)| *c).collect(),
+            power,
+        }
+    }
+
+    /// returns the roots of largest modulus which contribute to the sequence
+    pub fn dominant_roots(&self) -> &[Complex<f64>] {
+        &self.dominant_roots
+    }
+
+    /// returns the coefficient C of n^k r^n for each dominant root r
+    pub fn coefficients(&self) -> &[Complex<f64>] {
+        &self.coefficients
+    }
+
+    /// returns the power k of n in the leading behaviour
+    pub fn power(&self) -> usize {
+        self.power
+    }
+
+    /// returns the modulus of the dominant roots, the exponential growth rate of the sequence
+    pub fn modulus(&self) -> f64 {
+        self.dominant_roots.first().map_or(0.0, |root| root.norm())
+    }
+
+    /// returns whether the terms grow, stay bounded, decay or vanish
+    pub fn growth(&self) -> Growth {
+        let modulus = self.modulus();
+        if self.dominant_roots.is_empty() {
+            Growth::Zero
+        } else if modulus > 1.0 + TOLERANCE || modulus >= 1.0 - TOLERANCE && self.power > 0 {
+            Growth::Grows
+        } else if modulus >= 1.0 - TOLERANCE {
+            Growth::Bounded
+        } else {
+            Growth::Decays
+        }
+    }
+
+    /// returns whether the sign of the leading behaviour keeps changing,
+    /// which happens when a dominant root is negative or complex
+    pub fn oscillates(&self) -> bool {
+        self.dominant_roots
+            .iter()
+            .any(|root| root.re < 0.0 || root.im.abs() > TOLERANCE * root.norm())
+    }
+
+    /// returns the leading term C·n^k·r^n as (C, k, r) when there is a single real dominant root
+    pub fn leading_term(&self) -> Option<(f64, usize, f64)> {
+        match (&self.dominant_roots[..], &self.coefficients[..]) {
+            ([root], [coefficient]) if root.im.abs() <= TOLERANCE * root.norm() => {
+                Some((coefficient.re, self.power, root.re))
+            }
+            _ => None,
+        }
+    }
+
+    /// returns the limit of f(n+1)/f(n), which exists when there is a single real dominant root
+    pub fn ratio_limit(&self) -> Option<f64> {
+        self.leading_term().map(|(_, _, root)| root)
+    }
+
+    /// returns the order of growth of |f(n)|, as Θ(n^k·r^n) with r the modulus of the dominant
+    /// roots, or as O(n^k·r^n) when complex dominant roots let |f(n)| come close to zero infinitely often
+    pub fn big_theta(&self) -> String {
+        if self.growth() == Growth::Zero {
+            return "Θ(0)".to_string();
+        }
+        let mut factors = Vec::new();
+        match self.power {
+            0 => {}
+            1 => factors.push("n".to_string()),
+            k => factors.push(format!("n^{}", k)),
+        }
+        let modulus = number(self.modulus(), 3);
+        if modulus != "1" {
+            factors.push(format!("{}^n", modulus));
+        }
+        if factors.is_empty() {
+            factors.push("1".to_string());
+        }
+        let bound = if self.leading_term().is_some() {
+            "Θ"
+        } else {
+            "O"
+        };
+        format!("{}({})", bound, factors.join("·"))
+    }
+}
+
+impl fmt::Display for Asymptotics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let growth = match self.growth() {
+            Growth::Grows => "grows",
+            Growth::Bounded => "bounded",
+            Growth::Decays => "decays",
+            Growth::Zero => "eventually zero",
+        };
+        write!(f, "{}, {}", self.big_theta(), growth)?;
+        if self.oscillates() {
+            write!(f, " and oscillates")?;
+        }
+        if let Some((coefficient, power, root)) = self.leading_term() {
+            let mut factors = vec![number(coefficient, 3)];
+            match power {
+                0 => {}
+                1 => factors.push("n".to_string()),
+                k => factors.push(format!("n^{}", k)),
+            }
+            let root = number(root, 3);
+            if root != "1" {
+                factors.push(if root.starts_with('-') {
+                    format!("({})^n", root)
+                } else {
+                    format!("{}^n", root)
+                });
+            }
+            write!(
+                f,
+                ", f(n) ~ {}, f(n+1)/f(n) → {}",
+                factors.join("·"),
+                number(self.ratio_limit().unwrap(), 3)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns the leading behaviour of the sequence
+    /// the relation is minimized first, so roots whose terms cancel out are not counted
+    pub fn asymptotics(&self) -> Asymptotics {
+        let minimal = self.minimize();
+        let degree = minimal.degree();
+        if degree == 0 {
+            return Asymptotics::from_terms(&[]);
+        }
+
+        // solve for the constants of the general solution over the complex numbers, since the
+        // dominant roots may not be real
+        let roots = minimal.characteristic_polynomial().complex_roots();
+        let mut elements = Vec::new();
+        for n in 0..degree {
+            for (root, count) in &roots {
+                for i in 0..*count {
+                    elements.push(root.powu(n as u32) * (n as f64).powi(i as i32));
+                }
+            }
+        }
+        let matrix = DMatrix::from_row_slice(degree, degree, &elements);
+        let base_cases: Vec<Complex<f64>> = minimal
+            .base_cases()
+            .iter()
+            .map(|x| Complex::new(*x, 0.0))
+            .collect();
+        let constants = matrix
+            .lu()
+            .solve(&DMatrix::from_row_slice(degree, 1, &base_cases))
+            .expect("cant solve given linear system");
+
+        let mut terms = Vec::new();
+        let mut index = 0;
+        for (root, count) in roots {
+            terms.push((root, constants.as_slice()[index..index + count].to_vec()));
+            index += count;
+        }
+        Asymptotics::from_terms(&terms)
+    }
+}
+
+impl RecurrenceSolution {
+    /// returns the leading behaviour of the solution
+    pub fn asymptotics(&self) -> Asymptotics {
+        let terms: Vec<(Complex<f64>, Vec<Complex<f64>>)> = self
+            .terms()
+            .iter()
+            .map(|(polynomial, root)| {
+                let coefficients = polynomial
+                    .coefficients()
+                    .iter()
+                    .map(|c| Complex::new(*c, 0.0))
+                    .collect();
+                (Complex::new(*root, 0.0), coefficients)
+            })
+            .collect();
+        Asymptotics::from_terms(&terms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asymptotics() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let asymptotics = fibonacci.asymptotics();
+        assert_eq!(asymptotics.growth(), Growth::Grows);
+        assert!(!asymptotics.oscillates());
+        assert_eq!(
+            asymptotics.to_string(),
+            "Θ(1.618^n), grows, f(n) ~ 0.447·1.618^n, f(n+1)/f(n) → 1.618"
+        );
+        assert_eq!(fibonacci.solve().asymptotics().big_theta(), "Θ(1.618^n)");
+
+        // (n/2 + 1)2^n
+        let relation = RecurrenceRelation::new(vec![1.0, 3.0], vec![4.0, -4.0]);
+        assert_eq!(
+            relation.asymptotics().to_string(),
+            "Θ(n·2^n), grows, f(n) ~ 0.5·n·2^n, f(n+1)/f(n) → 2"
+        );
+
+        // n, from a root of one with multiplicity two
+        let relation = RecurrenceRelation::new(vec![0.0, 1.0], vec![2.0, -1.0]);
+        assert_eq!(relation.asymptotics().big_theta(), "Θ(n)");
+        assert_eq!(relation.asymptotics().growth(), Growth::Grows);
+
+        // the constant sequence, where the root 2 of 3f(n-1) - 2f(n-2) does not contribute
+        let relation = RecurrenceRelation::new(vec![1.0, 1.0], vec![3.0, -2.0]);
+        assert_eq!(
+            relation.asymptotics().to_string(),
+            "Θ(1), bounded, f(n) ~ 1, f(n+1)/f(n) → 1"
+        );
+    }
+
+    #[test]
+    fn test_oscillation() {
+        let relation = RecurrenceRelation::new(vec![1.0], vec![-2.0]);
+        assert_eq!(
+            relation.asymptotics().to_string(),
+            "Θ(2^n), grows and oscillates, f(n) ~ 1·(-2)^n, f(n+1)/f(n) → -2"
+        );
+
+        // cos(nπ/2), with dominant roots i and -i
+        let relation = RecurrenceRelation::new(vec![1.0, 0.0], vec![0.0, -1.0]);
+        let asymptotics = relation.asymptotics();
+        assert_eq!(asymptotics.dominant_roots().len(), 2);
+        assert_eq!(asymptotics.ratio_limit(), None);
+        assert_eq!(asymptotics.to_string(), "O(1), bounded and oscillates");
+
+        // 2^(-n) + 3^(-n) with a lower order term
+        let relation = RecurrenceRelation::new(vec![2.0, 5.0 / 6.0], vec![5.0 / 6.0, -1.0 / 6.0]);
+        assert_eq!(relation.asymptotics().growth(), Growth::Decays);
+
+        let zero = RecurrenceRelation::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+        assert_eq!(zero.asymptotics().to_string(), "Θ(0), eventually zero");
+    }
+}
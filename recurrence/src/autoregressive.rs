@@ -0,0 +1,228 @@
+use crate::recurrence_relation::RecurrenceRelation;
+use nalgebra::{DMatrix, DVector};
+
+/// how the coefficients of an autoregressive model are estimated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArMethod {
+    /// solves the yule-walker equations from the sample autocovariances with levinson-durbin,
+    /// which always gives a stable model
+    YuleWalker,
+    /// minimizes the sum of squared one step prediction errors
+    LeastSquares,
+}
+
+/// the criterion used to choose the order of an autoregressive model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrderCriterion {
+    /// the akaike information criterion N ln σ² + 2p
+    Aic,
+    /// the bayesian information criterion N ln σ² + p ln N, which favours smaller models
+    Bic,
+}
+
+/// an autoregressive model x(n) = φ_1 x(n-1) + ... + φ_p x(n-p) + e(n) fitted to data,
+/// where e(n) is noise with variance σ²
+/// no mean is subtracted from the data, so the model is a recurrence for the data itself
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArModel {
+    relation: RecurrenceRelation,
+    noise_variance: f64,
+    observations: usize,
+}
+
+// returns the autocovariances r(0), ..., r(p) of the data about zero
+fn autocovariances(data: &[f64], p: usize) -> Vec<f64> {
+    let n = data.len() as f64;
+    (0..=p)
+        .map(|k| data.iter().zip(&data[k..]).map(|(x, y)| x * y).sum::<f64>() / n)
+        .collect()
+}
+
+// solves the yule-walker equations for the coefficients and the noise variance
+fn levinson_durbin(r: &[f64]) -> Option<(Vec<f64>, f64)> {
+    let p = r.len() - 1;
+    let mut phi: Vec<f64> = Vec::new();
+    let mut variance = r[0];
+    for k in 1..=p {
+        if variance <= 0.0 {
+            return None;
+        }
+        // the reflection coefficient for order k
+        let mut kappa = r[k];
+        for (j, coefficient) in phi.iter().enumerate() {
+            kappa -= coefficient * r[k - j - 1];
+        }
+        kappa /= variance;
+
+        let mut next = Vec::new();
+        for j in 0..k - 1 {
+            next.push(phi[j] - kappa * phi[k - j - 2]);
+        }
+        next.push(kappa);
+        phi = next;
+        variance *= 1.0 - kappa * kappa;
+    }
+    Some((phi, variance))
+}
+
+// regresses each value on the p before it
+fn least_squares(data: &[f64], p: usize) -> Option<(Vec<f64>, f64)> {
+    // with no coefficients every value is a prediction error, and the normal equations are empty
+    if p == 0 {
+        let variance = data.iter().map(|x| x * x).sum::<f64>() / data.len() as f64;
+        return Some((Vec::new(), variance));
+    }
+    let rows = data.len() - p;
+    let x = DMatrix::from_fn(rows, p, |i, j| data[i + p - j - 1]);
+    let y = DVector::from_iterator(rows, data[p..].iter().copied());
+    let phi = (x.transpose() * &x).lu().solve(&(x.transpose() * &y))?;
+    let residuals = y - x * &phi;
+    Some((
+        phi.iter().copied().collect(),
+        residuals.norm_squared() / rows as f64,
+    ))
+}
+
+impl ArModel {
+    /// fits a model of order p to the data, with the first p values of the data as base cases
+    /// returns None if there are not more values than p or the equations are singular
+    pub fn fit(data: &[f64], p: usize, method: ArMethod) -> Option<ArModel> {
+        if data.len() <= p {
+            return None;
+        }
+        let (coefficients, noise_variance) = match method {
+            ArMethod::YuleWalker => levinson_durbin(&autocovariances(data, p))?,
+            ArMethod::LeastSquares => least_squares(data, p)?,
+        };
+        Some(ArModel {
+            relation: RecurrenceRelation::new(data[..p].to_vec(), coefficients),
+            noise_variance,
+            observations: data.len(),
+        })
+    }
+
+    /// fits models of every order up to max_order and returns the one with the smallest value
+    /// of the criterion, leaving out models whose criterion is NaN
+    pub fn select(
+        data: &[f64],
+        max_order: usize,
+        method: ArMethod,
+        criterion: OrderCriterion,
+    ) -> Option<ArModel> {
+        (0..=max_order)
+            .filter_map(|p| ArModel::fit(data, p, method))
+            .filter(|model| !model.criterion(criterion).is_nan())
+            .min_by(|a, b| a.criterion(criterion).total_cmp(&b.criterion(criterion)))
+    }
+
+    /// returns the fitted recurrence, which can be solved or checked for stability
+    pub fn relation(&self) -> &RecurrenceRelation {
+        &self.relation
+    }
+
+    /// returns the order p of the model
+    pub fn order(&self) -> usize {
+        self.relation.degree()
+    }
+
+    /// returns the coefficients φ_1, ..., φ_p
+    pub fn coefficients(&self) -> &[f64] {
+        self.relation.recurrence_coefficients()
+    }
+
+    /// returns the estimated variance σ² of the noise
+    pub fn noise_variance(&self) -> f64 {
+        self.noise_variance
+    }
+
+    /// returns N ln σ² + 2p, where N is the length of the data
+    pub fn aic(&self) -> f64 {
+        self.observations as f64 * self.noise_variance.ln() + 2.0 * self.order() as f64
+    }
+
+    /// returns N ln σ² + p ln N, where N is the length of the data
+    pub fn bic(&self) -> f64 {
+        let n = self.observations as f64;
+        n * self.noise_variance.ln() + self.order() as f64 * n.ln()
+    }
+
+    /// returns the value of the given criterion, smaller is better
+    pub fn criterion(&self, criterion: OrderCriterion) -> f64 {
+        match criterion {
+            OrderCriterion::Aic => self.aic(),
+            OrderCriterion::Bic => self.bic(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x(n) = 0.6x(n-1) - 0.3x(n-2) + e(n) with uniform noise from a linear congruential generator
+    fn noisy_ar2(n: usize) -> Vec<f64> {
+        let mut state: u64 = 12345;
+        let mut data = vec![0.0, 0.0];
+        for i in 2..n {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let noise = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+            data.push(0.6 * data[i - 1] - 0.3 * data[i - 2] + noise);
+        }
+        data
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = noisy_ar2(5000);
+        for method in [ArMethod::YuleWalker, ArMethod::LeastSquares] {
+            let model = ArModel::fit(&data, 2, method).unwrap();
+            assert!((model.coefficients()[0] - 0.6).abs() < 0.05);
+            assert!((model.coefficients()[1] + 0.3).abs() < 0.05);
+            // uniform noise on [-1/2, 1/2] has variance 1/12
+            assert!((model.noise_variance() - 1.0 / 12.0).abs() < 0.01);
+            assert_eq!(model.relation().base_cases(), &data[..2]);
+        }
+
+        // noise free data is fitted exactly by least squares
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let model = ArModel::fit(&fibonacci.get_terms(20), 2, ArMethod::LeastSquares).unwrap();
+        assert!((model.coefficients()[0] - 1.0).abs() < 1e-6);
+        assert!((model.coefficients()[1] - 1.0).abs() < 1e-6);
+
+        assert_eq!(ArModel::fit(&[1.0, 2.0], 2, ArMethod::YuleWalker), None);
+    }
+
+    #[test]
+    fn test_select() {
+        let data = noisy_ar2(5000);
+        for method in [ArMethod::YuleWalker, ArMethod::LeastSquares] {
+            for criterion in [OrderCriterion::Aic, OrderCriterion::Bic] {
+                let model = ArModel::select(&data, 8, method, criterion).unwrap();
+                assert_eq!(model.order(), 2);
+            }
+        }
+
+        // noise free data, which least squares fits exactly above order 0
+        let geometric: Vec<f64> = (0..20).map(|n| 2f64.powi(n)).collect();
+        let model =
+            ArModel::select(&geometric, 3, ArMethod::LeastSquares, OrderCriterion::Bic).unwrap();
+        assert!(model.order() >= 1);
+
+        // order 0 predicts every value as 0, leaving the mean square as the noise variance
+        let model = ArModel::fit(&[1.0, -1.0, 3.0], 0, ArMethod::LeastSquares).unwrap();
+        assert_eq!(model.coefficients(), &[] as &[f64]);
+        assert_eq!(model.noise_variance(), 11.0 / 3.0);
+
+        let mut data = noisy_ar2(100);
+        data[50] = f64::NAN;
+        assert_eq!(
+            ArModel::select(&data, 4, ArMethod::YuleWalker, OrderCriterion::Aic),
+            None
+        );
+    }
+}
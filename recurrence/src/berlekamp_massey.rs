@@ -0,0 +1,174 @@
+use crate::rational_polynomial::{rational_to_f64, to_rational, Rational};
+use crate::recurrence_relation::RecurrenceRelation;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero};
+
+// relative size below which a floating discrepancy is treated as zero
+const TOLERANCE: f64 = 1e-9;
+
+// the arithmetic berlekamp-massey needs, failing on overflow
+trait Scalar: Copy {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn add(self, other: Self) -> Option<Self>;
+    fn sub(self, other: Self) -> Option<Self>;
+    fn mul(self, other: Self) -> Option<Self>;
+    fn div(self, other: Self) -> Option<Self>;
+    fn is_negligible(self, scale: f64) -> bool;
+    fn magnitude(self) -> f64;
+}
+
+impl Scalar for f64 {
+    fn zero() -> f64 {
+        0.0
+    }
+    fn one() -> f64 {
+        1.0
+    }
+    fn add(self, other: f64) -> Option<f64> {
+        Some(self + other)
+    }
+    fn sub(self, other: f64) -> Option<f64> {
+        Some(self - other)
+    }
+    fn mul(self, other: f64) -> Option<f64> {
+        Some(self * other)
+    }
+    fn div(self, other: f64) -> Option<f64> {
+        Some(self / other)
+    }
+    fn is_negligible(self, scale: f64) -> bool {
+        self.abs() <= TOLERANCE * scale.max(1.0)
+    }
+    fn magnitude(self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Scalar for Rational {
+    fn zero() -> Rational {
+        Zero::zero()
+    }
+    fn one() -> Rational {
+        One::one()
+    }
+    fn add(self, other: Rational) -> Option<Rational> {
+        self.checked_add(&other)
+    }
+    fn sub(self, other: Rational) -> Option<Rational> {
+        self.checked_sub(&other)
+    }
+    fn mul(self, other: Rational) -> Option<Rational> {
+        self.checked_mul(&other)
+    }
+    fn div(self, other: Rational) -> Option<Rational> {
+        self.checked_div(&other)
+    }
+    fn is_negligible(self, _: f64) -> bool {
+        self.is_zero()
+    }
+    fn magnitude(self) -> f64 {
+        rational_to_f64(&self).abs()
+    }
+}
+
+// returns the coefficients of the shortest linear recurrence generating the terms
+// returns None if the arithmetic overflows
+fn berlekamp_massey<T: Scalar>(terms: &[T]) -> Option<Vec<T>> {
+    // connection polynomials, with terms[n] + c_1 terms[n-1] + ... + c_l terms[n-l] = 0
+    let mut current = vec![T::one()];
+    let mut previous = vec![T::one()];
+    let mut length = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = T::one();
+
+    for n in 0..terms.len() {
+        let mut discrepancy = terms[n];
+        let mut scale = terms[n].magnitude();
+        for i in 1..=length {
+            let product = current[i].mul(terms[n - i])?;
+            scale += product.magnitude();
+            discrepancy = discrepancy.add(product)?;
+        }
+        if discrepancy.is_negligible(scale) {
+            shift += 1;
+            continue;
+        }
+
+        let factor = discrepancy.div(previous_discrepancy)?;
+        let mut next = current.clone();
+        if next.len() < previous.len() + shift {
+            next.resize(previous.len() + shift, T::zero());
+        }
+        for (i, c) in previous.iter().enumerate() {
+            next[i + shift] = next[i + shift].sub(factor.mul(*c)?)?;
+        }
+        if 2 * length <= n {
+            previous = current;
+            length = n + 1 - length;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        current = next;
+    }
+
+    current.resize(length + 1, T::zero());
+    current[1..]
+        .iter()
+        .map(|c| T::zero().sub(*c))
+        .collect::<Option<Vec<T>>>()
+}
+
+/// returns the minimal recurrence relation generating the given terms
+/// the terms must include at least twice the order of the sequence for the result to be exact,
+/// so the operations and transforms on sequences, which know a bound d on the order of their
+/// result, compute 2d terms of it and find the relation through them here
+/// rational terms are handled exactly, falling back to floating point if they are not rational
+pub(crate) fn minimal_relation(terms: &[f64]) -> RecurrenceRelation {
+    let exact = terms
+        .iter()
+        .map(|x| to_rational(*x))
+        .collect::<Option<Vec<Rational>>>()
+        .and_then(|rationals| berlekamp_massey(&rationals))
+        .map(|coefficients| coefficients.iter().map(rational_to_f64).collect());
+    let recurrence_coefficients: Vec<f64> = match exact {
+        Some(coefficients) => coefficients,
+        None => berlekamp_massey(terms).unwrap(),
+    };
+    let base_cases = terms[..recurrence_coefficients.len()].to_vec();
+    RecurrenceRelation::new(base_cases, recurrence_coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_relation() {
+        let fibonacci = vec![0.0, 1.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0];
+        assert_eq!(
+            minimal_relation(&fibonacci),
+            RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0])
+        );
+
+        let powers = vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0];
+        assert_eq!(
+            minimal_relation(&powers),
+            RecurrenceRelation::new(vec![1.0], vec![2.0])
+        );
+
+        let zeros = vec![0.0; 6];
+        assert_eq!(
+            minimal_relation(&zeros),
+            RecurrenceRelation::new(vec![], vec![])
+        );
+
+        // powers of the golden ratio are not rational so are handled in floating point
+        let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        let golden: Vec<f64> = (0..6).map(|n| phi.powi(n)).collect();
+        let relation = minimal_relation(&golden);
+        assert_eq!(relation.degree(), 1);
+        assert!((relation.get_terms(6)[5] - golden[5]).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,350 @@
+use crate::expression::{EvaluationError, Expression};
+use crate::nonlinear::NonlinearRecurrence;
+use crate::stability::Stability;
+use std::collections::HashMap;
+
+// the number of intervals searched for sign changes of g^k(x) - x
+const SEARCH_INTERVALS: usize = 2000;
+// relative distance within which two points of a map are taken to be the same
+const TOLERANCE: f64 = 1e-6;
+// relative distance from 1 within which a multiplier is taken to have size 1
+const MULTIPLIER_TOLERANCE: f64 = 1e-6;
+/// size within which a lyapunov exponent is taken to be 0, so the orbit is neutral, which covers
+/// the error of an estimate over about 10000 steps
+pub const LYAPUNOV_TOLERANCE: f64 = 1e-3;
+
+/// the one dimensional map g of a first order recurrence x(n) = g(x(n-1))
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IteratedMap {
+    expression: Expression,
+    parameters: HashMap<String, f64>,
+}
+
+/// a periodic orbit x_0, g(x_0), ..., x_{k-1} of a map, with g^k(x_0) = x_0 for its period k
+/// and no smaller k
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cycle {
+    points: Vec<f64>,
+    multiplier: f64,
+}
+
+// returns whether x and y are the same point up to the tolerance
+fn same_point(x: f64, y: f64) -> bool {
+    (x - y).abs() <= TOLERANCE * x.abs().max(1.0)
+}
+
+impl Cycle {
+    /// returns the points of the cycle, starting from the smallest
+    pub fn points(&self) -> &[f64] {
+        &self.points
+    }
+
+    pub fn period(&self) -> usize {
+        self.points.len()
+    }
+
+    /// returns the derivative of g^k at any point of the cycle, the product of g' over its points
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// returns whether orbits starting near the cycle approach it, which they do when the
+    /// multiplier has size less than 1 and do not when it has size more than 1
+    pub fn stability(&self) -> Stability {
+        let size = self.multiplier.abs();
+        if size < 1.0 - MULTIPLIER_TOLERANCE {
+            Stability::Stable
+        } else if size > 1.0 + MULTIPLIER_TOLERANCE {
+            Stability::Unstable
+        } else {
+            Stability::MarginallyStable
+        }
+    }
+}
+
+impl IteratedMap {
+    /// returns the values of the named parameters
+    pub fn parameters(&self) -> &HashMap<String, f64> {
+        &self.parameters
+    }
+
+    pub fn set_parameter(&mut self, name: &str, value: f64) {
+        self.parameters.insert(name.to_string(), value);
+    }
+
+    /// returns g(x)
+    pub fn evaluate(&self, x: f64) -> Result<f64, EvaluationError> {
+        // the expression only uses f(n-1), so the index does not matter
+        self.expression
+            .evaluate(1, &self.parameters, &mut |_| Ok(x))
+    }
+
+    /// returns g^k(x), g applied k times to x
+    pub fn iterate(&self, x: f64, k: usize) -> Result<f64, EvaluationError> {
+        let mut x = x;
+        for _ in 0..k {
+            x = self.evaluate(x)?;
+        }
+        Ok(x)
+    }
+
+    /// returns g'(x) by a central difference
+    pub fn derivative(&self, x: f64) -> Result<f64, EvaluationError> {
+        let h = 1e-6 * x.abs().max(1.0);
+        Ok((self.evaluate(x + h)? - self.evaluate(x - h)?) / (2.0 * h))
+    }
+
+    /// returns the first n points of the orbit x, g(x), g(g(x)), ...
+    pub fn orbit(&self, x: f64, n: usize) -> Result<Vec<f64>, EvaluationError> {
+        let mut orbit = Vec::with_capacity(n);
+        let mut x = x;
+        for i in 0..n {
+            if i > 0 {
+                x = self.evaluate(x)?;
+            }
+            orbit.push(x);
+        }
+        Ok(orbit)
+    }
+
+    /// returns the cycles of the given period with a point in [low, high], ordered by their
+    /// smallest point
+    /// the points are the roots of g^k(x) - x found by bisection where it changes sign,
+    /// so cycles where it touches zero without crossing, such as at a bifurcation, are missed
+    pub fn cycles(
+        &self,
+        period: usize,
+        low: f64,
+        high: f64,
+    ) -> Result<Vec<Cycle>, EvaluationError> {
+        assert!(period > 0, "a cycle has at least one point");
+        let f = |x: f64| Ok(self.iterate(x, period)? - x);
+
+        let grid: Vec<f64> = (0..=SEARCH_INTERVALS)
+            .map(|i| low + (high - low) * i as f64 / SEARCH_INTERVALS as f64)
+            .collect();
+        let values = grid
+            .iter()
+            .map(|x| f(*x))
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        let mut roots = Vec::new();
+        for i in 0..grid.len() {
+            if values[i] == 0.0 {
+                roots.push(grid[i]);
+                continue;
+            }
+            let crosses = i + 1 < grid.len() && values[i] * values[i + 1] < 0.0;
+            if !crosses {
+                continue;
+            }
+            let (mut a, mut b) = (grid[i], grid[i + 1]);
+            let mut f_a = values[i];
+            for _ in 0..100 {
+                let middle = (a + b) / 2.0;
+                let f_middle = f(middle)?;
+                if f_middle == 0.0 || middle == a || middle == b {
+                    a = middle;
+                    b = middle;
+                    break;
+                }
+                if (f_middle < 0.0) == (f_a < 0.0) {
+                    a = middle;
+                    f_a = f_middle;
+                } else {
+                    b = middle;
+                }
+            }
+            let root = (a + b) / 2.0;
+            // a sign change across a pole of g is not a root
+            if same_point(self.iterate(root, period)?, root) {
+                roots.push(root);
+            }
+        }
+
+        let mut cycles: Vec<Cycle> = Vec::new();
+        for root in roots {
+            if cycles
+                .iter()
+                .any(|cycle| cycle.points.iter().any(|x| same_point(*x, root)))
+            {
+                continue;
+            }
+            let mut points = self.orbit(root, period)?;
+            // the orbit returns to the root early when its period is smaller
+            if points[1..].iter().any(|x| same_point(*x, root)) {
+                continue;
+            }
+            let smallest = (0..period)
+                .min_by(|i, j| points[*i].partial_cmp(&points[*j]).unwrap())
+                .unwrap();
+            points.rotate_left(smallest);
+            let mut multiplier = 1.0;
+            for x in &points {
+                multiplier *= self.derivative(*x)?;
+            }
+            cycles.push(Cycle { points, multiplier });
+        }
+        cycles.sort_by(|a, b| a.points[0].partial_cmp(&b.points[0]).unwrap());
+        Ok(cycles)
+    }
+
+    /// returns the fixed points g(x) = x in [low, high], as cycles of period 1
+    pub fn fixed_points(&self, low: f64, high: f64) -> Result<Vec<Cycle>, EvaluationError> {
+        self.cycles(1, low, high)
+    }
+
+    /// returns the lyapunov exponent, the average of ln|g'(x)| over n points of the orbit of x
+    /// after the first transient points are dropped
+    /// it is positive for chaotic orbits and negative for orbits approaching a stable cycle
+    pub fn lyapunov_exponent(
+        &self,
+        x: f64,
+        transient: usize,
+        n: usize,
+    ) -> Result<f64, EvaluationError> {
+        let mut x = self.iterate(x, transient)?;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            sum += self.derivative(x)?.abs().ln();
+            x = self.evaluate(x)?;
+        }
+        Ok(sum / n as f64)
+    }
+
+    /// returns the points of a bifurcation diagram, for each value of the parameter the
+    /// given number of points of the orbit of x after the first transient points are dropped
+    /// points which are not finite are left out
+    pub fn bifurcation_diagram(
+        &self,
+        parameter: &str,
+        values: &[f64],
+        x: f64,
+        transient: usize,
+        samples: usize,
+    ) -> Result<Vec<(f64, Vec<f64>)>, EvaluationError> {
+        let mut map = self.clone();
+        values
+            .iter()
+            .map(|value| {
+                map.set_parameter(parameter, *value);
+                let start = map.iterate(x, transient)?;
+                let mut orbit = map.orbit(start, samples)?;
+                orbit.retain(|x| x.is_finite());
+                Ok((*value, orbit))
+            })
+            .collect()
+    }
+
+    /// returns the bifurcation diagram as csv with the columns parameter and x, one row per point
+    pub fn bifurcation_diagram_csv(
+        &self,
+        parameter: &str,
+        values: &[f64],
+        x: f64,
+        transient: usize,
+        samples: usize,
+    ) -> Result<String, EvaluationError> {
+        let mut csv = "parameter,x\n".to_string();
+        for (value, orbit) in self.bifurcation_diagram(parameter, values, x, transient, samples)? {
+            for x in orbit {
+                csv.push_str(&format!("{},{}\n", value, x));
+            }
+        }
+        Ok(csv)
+    }
+}
+
+impl NonlinearRecurrence {
+    /// returns the map g when the recurrence has the form x(n) = g(x(n-1)), using only the
+    /// previous term and not n itself
+    pub fn map(&self) -> Option<IteratedMap> {
+        if !self.expression().is_first_order() {
+            return None;
+        }
+        Some(IteratedMap {
+            expression: self.expression().clone(),
+            parameters: self.parameters().clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::assert_within;
+
+    fn logistic(r: f64) -> IteratedMap {
+        let mut recurrence: NonlinearRecurrence =
+            "x(n) = r*x(n-1)*(1-x(n-1)), x(0) = 0.5".parse().unwrap();
+        recurrence.set_parameter("r", r);
+        recurrence.map().unwrap()
+    }
+
+    #[test]
+    fn test_cycles() {
+        // the fixed points of x -> 2.5x(1-x) are 0 with multiplier 2.5 and 0.6 with multiplier -0.5
+        let fixed_points = logistic(2.5).fixed_points(-1.0, 2.0).unwrap();
+        assert_eq!(fixed_points.len(), 2);
+        assert_within(fixed_points[0].points()[0], 0.0);
+        assert_within(fixed_points[0].multiplier(), 2.5);
+        assert_eq!(fixed_points[0].stability(), Stability::Unstable);
+        assert_within(fixed_points[1].points()[0], 0.6);
+        assert_within(fixed_points[1].multiplier(), -0.5);
+        assert_eq!(fixed_points[1].stability(), Stability::Stable);
+
+        // at r = 3.2 the 2-cycle is (r + 1 ± sqrt((r - 3)(r + 1)))/2r with multiplier
+        // -r^2 + 2r + 4, and the fixed points are not counted again
+        let r: f64 = 3.2;
+        let cycles = logistic(r).cycles(2, 0.0, 1.0).unwrap();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].period(), 2);
+        let root = ((r - 3.0) * (r + 1.0)).sqrt();
+        assert_within(cycles[0].points()[0], (r + 1.0 - root) / (2.0 * r));
+        assert_within(cycles[0].points()[1], (r + 1.0 + root) / (2.0 * r));
+        assert_within(cycles[0].multiplier(), -r * r + 2.0 * r + 4.0);
+        assert_eq!(cycles[0].stability(), Stability::Stable);
+
+        // only first order recurrences give a map
+        let fibonacci: NonlinearRecurrence = "f(n) = f(n-1) + f(n-2) mod 7, f(0) = 0, f(1) = 1"
+            .parse()
+            .unwrap();
+        assert_eq!(fibonacci.map(), None);
+        let indexed: NonlinearRecurrence = "f(n) = f(n-1) + n, f(0) = 0".parse().unwrap();
+        assert_eq!(indexed.map(), None);
+    }
+
+    #[test]
+    fn test_lyapunov_exponent() {
+        // ln|g'(0.6)| = ln 0.5 once the orbit settles on the stable fixed point
+        let exponent = logistic(2.5).lyapunov_exponent(0.3, 1000, 1000).unwrap();
+        assert_within(exponent, 0.5f64.ln());
+        // the fully chaotic logistic map has exponent ln 2
+        let exponent = logistic(4.0).lyapunov_exponent(0.3, 1000, 100_000).unwrap();
+        assert!((exponent - 2f64.ln()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bifurcation_diagram() {
+        let diagram = logistic(2.0)
+            .bifurcation_diagram("r", &[2.5, 3.2, 3.5], 0.5, 1000, 8)
+            .unwrap();
+        // one, two and four distinct points as the period doubles
+        for ((value, orbit), count) in diagram.iter().zip(vec![1, 2, 4]) {
+            let mut distinct: Vec<f64> = Vec::new();
+            for x in orbit {
+                if !distinct.iter().any(|y| same_point(*x, *y)) {
+                    distinct.push(*x);
+                }
+            }
+            assert_eq!(distinct.len(), count, "r = {}", value);
+        }
+        let csv = logistic(2.0)
+            .bifurcation_diagram_csv("r", &[2.5, 3.2], 0.5, 1000, 8)
+            .unwrap();
+        assert_eq!(csv.lines().count(), 17);
+        assert!(csv.starts_with("parameter,x\n2.5,0.6"));
+    }
+}
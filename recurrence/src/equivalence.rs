@@ -0,0 +1,207 @@
+use crate::rational_polynomial::{rational_to_f64, to_rational, Rational};
+use crate::recurrence_relation::RecurrenceRelation;
+use num_traits::{CheckedAdd, CheckedMul, Zero};
+use std::fmt;
+
+// relative tolerance used when the terms are not rational
+const TOLERANCE: f64 = 1e-9;
+
+/// the outcome of comparing the sequences generated by two recurrence relations
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Equivalence {
+    /// the sequences agree on their first terms_checked terms
+    /// this proves they are equal, since their difference satisfies a recurrence of degree
+    /// left_degree + right_degree and so is zero once that many of its terms are
+    Equivalent {
+        left_degree: usize,
+        right_degree: usize,
+        terms_checked: usize,
+    },
+    /// the sequences agree on their first terms_checked terms up to the relative tolerance 1e-9
+    /// the terms could not be computed exactly, so this is evidence of equality but not a proof
+    NumericallyEquivalent {
+        left_degree: usize,
+        right_degree: usize,
+        terms_checked: usize,
+    },
+    /// the sequences first differ at index
+    Different { index: usize, left: f64, right: f64 },
+}
+
+impl Equivalence {
+    /// returns whether the sequences agree, exactly or up to the tolerance
+    pub fn is_equivalent(&self) -> bool {
+        matches!(
+            self,
+            Equivalence::Equivalent { .. } | Equivalence::NumericallyEquivalent { .. }
+        )
+    }
+}
+
+// returns the first n terms in exact arithmetic
+// returns None if a coefficient or base case is not rational or the arithmetic overflows
+fn exact_terms(relation: &RecurrenceRelation, n: usize) -> Option<Vec<Rational>> {
+    let to_rationals = |xs: &[f64]| {
+        xs.iter()
+            .map(|x| to_rational(*x))
+            .collect::<Option<Vec<_>>>()
+    };
+    let coefficients = to_rationals(relation.recurrence_coefficients())?;
+    let mut terms = to_rationals(relation.base_cases())?;
+    while terms.len() < n {
+        let mut term = Rational::zero();
+        for (i, coefficient) in coefficients.iter().enumerate() {
+            term = term.checked_add(&coefficient.checked_mul(&terms[terms.len() - 1 - i])?)?;
+        }
+        terms.push(term);
+    }
+    terms.truncate(n);
+    Some(terms)
+}
+
+// returns the first index where the sequences differ
+fn first_difference<T, F>(left: &[T], right: &[T], same: F) -> Option<usize>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    (0..left.len().min(right.len())).find(|i| !same(&left[*i], &right[*i]))
+}
+
+impl RecurrenceRelation {
+    /// decides whether the two relations generate the same sequence, returning a certificate
+    /// either the number of agreeing terms that proves equality or the first index where they differ
+    /// the terms are compared exactly when the coefficients and base cases are rational, and
+    /// otherwise up to a relative tolerance, which only shows the sequences are numerically equal
+    pub fn equivalence(&self, other: &RecurrenceRelation) -> Equivalence {
+        let terms_checked = self.degree() + other.degree();
+        let (left_degree, right_degree) = (self.degree(), other.degree());
+        if let (Some(left), Some(right)) = (
+            exact_terms(self, terms_checked),
+            exact_terms(other, terms_checked),
+        ) {
+            return match first_difference(&left, &right, |x, y| x == y) {
+                Some(index) => Equivalence::Different {
+                    index,
+                    left: rational_to_f64(&left[index]),
+                    right: rational_to_f64(&right[index]),
+                },
+                None => Equivalence::Equivalent {
+                    left_degree,
+                    right_degree,
+                    terms_checked,
+                },
+            };
+        }
+
+        let left = self.get_terms(terms_checked);
+        let right = other.get_terms(terms_checked);
+        let same = |x: &f64, y: &f64| (x - y).abs() <= TOLERANCE * x.abs().max(y.abs()).max(1.0);
+        match first_difference(&left, &right, same) {
+            Some(index) => Equivalence::Different {
+                index,
+                left: left[index],
+                right: right[index],
+            },
+            None => Equivalence::NumericallyEquivalent {
+                left_degree,
+                right_degree,
+                terms_checked,
+            },
+        }
+    }
+
+    /// returns whether the two relations generate the same sequence
+    pub fn is_equivalent(&self, other: &RecurrenceRelation) -> bool {
+        self.equivalence(other).is_equivalent()
+    }
+}
+
+impl fmt::Display for Equivalence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Equivalence::Equivalent {
+                left_degree,
+                right_degree,
+                terms_checked,
+            } => write!(
+                f,
+                "equivalent: the first {} terms agree, and the difference of sequences of degree {} and {} \
+                 satisfies a recurrence of degree at most {}, so it is zero",
+                terms_checked, left_degree, right_degree, terms_checked
+            ),
+            Equivalence::NumericallyEquivalent { terms_checked, .. } => write!(
+                f,
+                "numerically equivalent: the first {} terms agree to within a relative error of {}, \
+                 which would prove equality if they could be compared exactly",
+                terms_checked, TOLERANCE
+            ),
+            Equivalence::Different { index, left, right } => write!(
+                f,
+                "not equivalent: f({}) = {} but g({}) = {}",
+                index, left, index, right
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equivalence() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        // F(n) = 2F(n-2) + F(n-3) also generates the fibonacci numbers
+        let other = RecurrenceRelation::new(vec![0.0, 1.0, 1.0], vec![0.0, 2.0, 1.0]);
+        assert_eq!(
+            fibonacci.equivalence(&other),
+            Equivalence::Equivalent {
+                left_degree: 2,
+                right_degree: 3,
+                terms_checked: 5
+            }
+        );
+        assert!(other.is_equivalent(&fibonacci));
+
+        let lucas = RecurrenceRelation::new(vec![2.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(
+            fibonacci.equivalence(&lucas),
+            Equivalence::Different {
+                index: 0,
+                left: 0.0,
+                right: 2.0
+            }
+        );
+
+        // agrees with the fibonacci numbers on the first six terms only
+        let impostor = RecurrenceRelation::new(
+            vec![0.0, 1.0, 1.0, 2.0, 3.0],
+            vec![1.0, 1.0, 0.0, 0.0, 0.001],
+        );
+        assert_eq!(
+            fibonacci.equivalence(&impostor),
+            Equivalence::Different {
+                index: 6,
+                left: 8.0,
+                right: 8.001
+            }
+        );
+
+        // irrational coefficients can only be compared up to rounding
+        let sqrt2 = std::f64::consts::SQRT_2;
+        let left = RecurrenceRelation::new(vec![1.0, sqrt2], vec![0.0, 2.0]);
+        let right = RecurrenceRelation::new(vec![1.0], vec![sqrt2]);
+        assert_eq!(
+            left.equivalence(&right),
+            Equivalence::NumericallyEquivalent {
+                left_degree: 2,
+                right_degree: 1,
+                terms_checked: 3
+            }
+        );
+        assert!(left.is_equivalent(&right));
+        let other = RecurrenceRelation::new(vec![1.0], vec![sqrt2 + 1e-6]);
+        assert!(!left.is_equivalent(&other));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::algebraic::{roots_in_radicals, Algebraic};
+use crate::rational_polynomial::{Rational, RationalPolynomial};
+use num_complex::Complex;
+use num_traits::{CheckedDiv, CheckedMul, CheckedSub, One, Zero};
+use std::fmt;
+
+/// an exact closed form of a recurrence relation
+/// each term is a polynomial in n with algebraic coefficients times an algebraic root to the n
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExactSolution {
+    terms: Vec<(Vec<Algebraic>, Algebraic)>,
+}
+
+// returns the sums of the k-th powers of the roots of a monic polynomial for k in 0..count
+// using newton's identities, with 0^0 = 1
+fn power_sums(polynomial: &RationalPolynomial, count: usize) -> Option<Vec<Rational>> {
+    let degree = polynomial.degree();
+    let c = polynomial.coefficients();
+    let mut sums = vec![Rational::from(degree as i128)];
+    for s in 1..count {
+        let mut sum = Rational::zero();
+        for t in 1..=s.min(degree) {
+            let previous = if t < s {
+                sums[s - t]
+            } else {
+                Rational::from(s as i128)
+            };
+            sum = sum.checked_sub(&c[degree - t].checked_mul(&previous)?)?;
+        }
+        sums.push(sum);
+    }
+    Some(sums)
+}
+
+// solves the square linear system exactly with gaussian elimination
+// returns None if the system is singular or the arithmetic overflows
+pub(crate) fn solve_rational_system(
+    mut matrix: Vec<Vec<Rational>>,
+    mut rhs: Vec<Rational>,
+) -> Option<Vec<Rational>> {
+    let size = rhs.len();
+    for column in 0..size {
+        let pivot = (column..size).find(|row| !matrix[*row][column].is_zero())?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+        for row in 0..size {
+            if row != column && !matrix[row][column].is_zero() {
+                let factor = matrix[row][column].checked_div(&matrix[column][column])?;
+                let pivot_row = matrix[column].clone();
+                for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+                    *value = value.checked_sub(&factor.checked_mul(&pivot_value)?)?;
+                }
+                let value = rhs[column];
+                rhs[row] = rhs[row].checked_sub(&factor.checked_mul(&value)?)?;
+            }
+        }
+    }
+    (0..size)
+        .map(|i| rhs[i].checked_div(&matrix[i][i]))
+        .collect()
+}
+
+impl ExactSolution {
+    pub fn new(terms: Vec<(Vec<Algebraic>, Algebraic)>) -> ExactSolution {
+        ExactSolution { terms }
+    }
+
+    /// returns the terms of the solution as polynomial coefficients, starting with the constant, and roots
+    pub fn terms(&self) -> &[(Vec<Algebraic>, Algebraic)] {
+        &self.terms
+    }
+
+    /// returns the closed form of the recurrence with the given characteristic polynomial and base cases
+    /// returns None if an irreducible factor of the polynomial has degree above four or the exact
+    /// arithmetic overflows
+    pub(crate) fn solve(
+        characteristic_polynomial: &RationalPolynomial,
+        base_cases: &[Rational],
+    ) -> Option<ExactSolution> {
+        let factors = characteristic_polynomial.factor()?;
+        let roots = factors
+            .iter()
+            .map(|(factor, _)| roots_in_radicals(factor))
+            .collect::<Option<Vec<Vec<Algebraic>>>>()?;
+
+        // the coefficient of n^i r^n for a root r of an irreducible factor g is q(r) for a rational
+        // polynomial q of degree below deg g shared by all roots of g, so summing over the roots of g
+        // turns each base case into a rational equation in power sums of those roots
+        let degree = base_cases.len();
+        let sums: Vec<Vec<Rational>> = factors
+            .iter()
+            .map(|(factor, _)| power_sums(factor, degree + factor.degree()))
+            .collect::<Option<_>>()?;
+        let mut matrix = vec![Vec::new(); degree];
+        for (n, row) in matrix.iter_mut().enumerate() {
+            for (k, (factor, multiplicity)) in factors.iter().enumerate() {
+                for i in 0..*multiplicity {
+                    for j in 0..factor.degree() {
+                        let n_power = if i == 0 {
+                            Rational::one()
+                        } else {
+                            Rational::from((n as i128).pow(i as u32))
+                        };
+                        row.push(n_power.checked_mul(&sums[k][n + j])?);
+                    }
+                }
+            }
+        }
+        let unknowns = solve_rational_system(matrix, base_cases.to_vec())?;
+
+        let mut terms = Vec::new();
+        let mut index = 0;
+        for ((factor, multiplicity), factor_roots) in factors.iter().zip(roots) {
+            let polynomials: Vec<RationalPolynomial> = (0..*multiplicity)
+                .map(|i| {
+                    let start = index + i * factor.degree();
+                    RationalPolynomial::new(unknowns[start..start + factor.degree()].to_vec())
+                })
+                .collect();
+            index += multiplicity * factor.degree();
+            for root in factor_roots {
+                let coefficients = polynomials
+                    .iter()
+                    .map(|q| root.substitute_into(q))
+                    .collect::<Option<Vec<Algebraic>>>()?;
+                terms.push((coefficients, root));
+            }
+        }
+        Some(ExactSolution::new(terms))
+    }
+
+    pub fn evaluate(&self, n: u32) -> f64 {
+        let mut res = Complex::new(0.0, 0.0);
+        for (coefficients, root) in &self.terms {
+            let power = root.evaluate().powu(n);
+            for (i, coefficient) in coefficients.iter().enumerate() {
+                res += coefficient.evaluate() * (n as f64).powi(i as i32) * power;
+            }
+        }
+        res.re
+    }
+
+    pub fn get_terms(&self, n: u32) -> Vec<f64> {
+        let mut res = Vec::new();
+        for i in 0..n {
+            res.push(self.evaluate(i));
+        }
+        res
+    }
+}
+
+// joins the factors of a term, writing parenthesized factors next to each other
+fn join_factors(factors: &[String]) -> String {
+    let mut res = String::new();
+    for factor in factors {
+        // n can follow an integer or a parenthesized factor directly
+        let before_n = res.ends_with(')') || res.chars().all(|c| c.is_ascii_digit());
+        if !(res.is_empty() || factor.starts_with('(') || before_n && factor.starts_with('n')) {
+            res.push('·');
+        }
+        res.push_str(factor);
+    }
+    res
+}
+
+impl fmt::Display for ExactSolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut res = String::new();
+        for (coefficients, root) in &self.terms {
+            for (i, coefficient) in coefficients.iter().enumerate() {
+                if coefficient.is_zero() {
+                    continue;
+                }
+                let negative = coefficient.is_negative_form();
+                let magnitude = if negative {
+                    -coefficient.clone()
+                } else {
+                    coefficient.clone()
+                };
+
+                let mut factors = Vec::new();
+                if !magnitude.is_one() {
+                    factors.push(magnitude.to_atomic_string());
+                }
+                if i == 1 {
+                    factors.push("n".to_string());
+                } else if i > 1 {
+                    factors.push(format!("n^{}", i));
+                }
+                if !root.is_one() {
+                    factors.push(format!("{}^n", root.to_atomic_string()));
+                }
+                if factors.is_empty() {
+                    factors.push("1".to_string());
+                }
+
+                if res.is_empty() {
+                    if negative {
+                        res.push('-');
+                    }
+                } else if negative {
+                    res.push_str(" - ");
+                } else {
+                    res.push_str(" + ");
+                }
+                res.push_str(&join_factors(&factors));
+            }
+        }
+        if res.is_empty() {
+            res.push('0');
+        }
+        write!(f, "{}", res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurrence_relation::RecurrenceRelation;
+    use crate::utilities::*;
+
+    #[test]
+    fn test_power_sums() {
+        // roots of x^2 - x - 1 have power sums equal to the lucas numbers
+        let golden = RationalPolynomial::from_integers(&[-1, -1, 1]);
+        let lucas: Vec<Rational> = [2, 1, 3, 4, 7, 11]
+            .iter()
+            .map(|x| Rational::from(*x))
+            .collect();
+        assert_eq!(power_sums(&golden, 6), Some(lucas));
+    }
+
+    #[test]
+    fn test_solve_exact() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let solution = fibonacci.solve_exact().unwrap();
+        assert_eq!(
+            solution.to_string(),
+            "(1/√5)((1+√5)/2)^n - (1/√5)((1-√5)/2)^n"
+        );
+        assert!(vec_within(solution.get_terms(10), fibonacci.get_terms(10)));
+
+        let relation = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 2.0]);
+        assert_eq!(
+            relation.solve_exact().unwrap().to_string(),
+            "(1/3)·2^n - (1/3)(-1)^n"
+        );
+
+        let relation = RecurrenceRelation::new(vec![1.0, -2.0, 3.0], vec![-6.0, -12.0, -8.0]);
+        let solution = relation.solve_exact().unwrap();
+        assert_eq!(
+            solution.to_string(),
+            "(-2)^n + (1/8)n(-2)^n - (1/8)n^2(-2)^n"
+        );
+        assert!(vec_within(solution.get_terms(8), relation.get_terms(8)));
+    }
+
+    #[test]
+    fn test_solve_exact_higher_degree() {
+        let relations = vec![
+            RecurrenceRelation::new(vec![1.0, 0.0, 0.0], vec![0.0, 0.0, 2.0]),
+            RecurrenceRelation::new(vec![0.0, 1.0, 1.0], vec![1.0, 1.0, 1.0]),
+            RecurrenceRelation::new(vec![0.0, 0.0, 0.0, 1.0], vec![1.0, 1.0, 1.0, 1.0]),
+            RecurrenceRelation::new(vec![1.0, 2.0, 3.0, 4.0], vec![0.0, 10.0, 0.0, -1.0]),
+            RecurrenceRelation::new(vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 1.0, 1.0]),
+        ];
+        for relation in relations {
+            let solution = relation.solve_exact().unwrap();
+            assert!(vec_within(solution.get_terms(12), relation.get_terms(12)));
+        }
+
+        // x^5 - x - 1 is irreducible and not solvable in radicals
+        let relation = RecurrenceRelation::new(vec![0.0; 5], vec![0.0, 0.0, 0.0, 1.0, 1.0]);
+        assert!(relation.solve_exact().is_none());
+
+        // cardano's formula overflows on these coefficients, so there is no exact solution
+        let relation =
+            RecurrenceRelation::new(vec![1.0, 2.0, 3.0], vec![0.123457, 0.234567, 0.345679]);
+        assert!(relation.solve_exact().is_none());
+
+        // the resolvent cubic of this quartic has a constant too large for the rational root test
+        let relation = RecurrenceRelation::new(vec![1.0, 2.0, 3.0, 4.0], vec![999983.0; 4]);
+        assert!(relation.solve_exact().is_none());
+    }
+}
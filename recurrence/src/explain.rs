@@ -0,0 +1,428 @@
+use crate::exact_solution::ExactSolution;
+use crate::format::{FormatOptions, Notation};
+use crate::latex::LatexOptions;
+use crate::polynomial::Polynomial;
+use crate::recurrence_relation::{general_solution, RecurrenceRelation};
+use crate::recurrence_solution::RecurrenceSolution;
+use std::fmt;
+
+/// the formats an explanation can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplanationFormat {
+    Text,
+    Markdown,
+    Latex,
+}
+
+/// the derivation of the solution of a recurrence relation, following the steps taken by solve
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    relation: RecurrenceRelation,
+    characteristic_polynomial: Polynomial,
+    roots: Vec<(f64, usize)>,
+    matrix: Vec<Vec<f64>>,
+    constants: Vec<f64>,
+    solution: RecurrenceSolution,
+    exact_solution: Option<ExactSolution>,
+}
+
+impl RecurrenceRelation {
+    /// returns the step by step derivation of the solution
+    pub fn explain(&self) -> Explanation {
+        let characteristic_polynomial = self.characteristic_polynomial();
+        let (roots, matrix, constants) = if self.degree() == 0 {
+            (Vec::new(), Vec::new(), Vec::new())
+        } else {
+            let roots = characteristic_polynomial.roots();
+            let matrix = self.base_case_matrix(&roots);
+            let rows = matrix
+                .row_iter()
+                .map(|row| row.iter().copied().collect())
+                .collect();
+            let constants = self.solve_constants(&roots);
+            (roots, rows, constants)
+        };
+        let solution = general_solution(&roots, &constants);
+        Explanation {
+            relation: self.clone(),
+            characteristic_polynomial,
+            roots,
+            matrix,
+            constants,
+            solution,
+            exact_solution: self.solve_exact(),
+        }
+    }
+}
+
+// writes the pieces of each step either as plain text or as latex
+struct Syntax {
+    latex: bool,
+    text_options: FormatOptions,
+    latex_options: LatexOptions,
+}
+
+impl Syntax {
+    fn new(latex: bool) -> Syntax {
+        Syntax {
+            latex,
+            text_options: FormatOptions::default(),
+            latex_options: LatexOptions::default(),
+        }
+    }
+
+    fn notation(&self) -> Notation<'_> {
+        if self.latex {
+            Notation::Latex(&self.latex_options)
+        } else {
+            Notation::Text(&self.text_options)
+        }
+    }
+
+    fn number(&self, x: f64) -> String {
+        self.notation().number(x)
+    }
+
+    fn join_terms(&self, terms: &[(bool, String)]) -> String {
+        self.notation().join_terms(terms)
+    }
+
+    fn product(&self, factors: &[String]) -> String {
+        let factors: Vec<&str> = factors
+            .iter()
+            .map(|f| f.as_str())
+            .filter(|f| !f.is_empty())
+            .collect();
+        factors.join(if self.latex { " \\cdot " } else { "·" })
+    }
+
+    fn parenthesized(&self, x: &str) -> String {
+        self.notation().parenthesized(x)
+    }
+
+    fn power(&self, base: &str, exponent: &str) -> String {
+        self.notation().power(base, exponent)
+    }
+
+    fn subscript(&self, name: &str, k: usize) -> String {
+        if self.latex {
+            format!("{}_{{{}}}", name, k)
+        } else {
+            format!("{}_{}", name, k)
+        }
+    }
+
+    fn f(&self, n: &str) -> String {
+        if self.latex {
+            format!("f_{{{}}}", n)
+        } else {
+            format!("f({})", n)
+        }
+    }
+
+    fn none(&self) -> String {
+        if self.latex {
+            "\\text{none}".to_string()
+        } else {
+            "none".to_string()
+        }
+    }
+
+    // r^n, left out when the root is one
+    fn root_power(&self, root: f64) -> String {
+        let r = self.number(root);
+        if r == self.number(1.0) {
+            String::new()
+        } else if root < 0.0 {
+            self.power(&self.parenthesized(&r), "n")
+        } else {
+            self.power(&r, "n")
+        }
+    }
+}
+
+impl Explanation {
+    /// returns the recurrence relation being solved
+    pub fn relation(&self) -> &RecurrenceRelation {
+        &self.relation
+    }
+
+    /// returns the characteristic polynomial, whose roots are the r with r^n solving the recurrence
+    pub fn characteristic_polynomial(&self) -> &Polynomial {
+        &self.characteristic_polynomial
+    }
+
+    /// returns the roots of the characteristic polynomial with their multiplicities
+    pub fn roots(&self) -> &[(f64, usize)] {
+        &self.roots
+    }
+
+    /// returns the matrix of the linear system for the constants of the general solution
+    /// row n evaluates each n^i r^n of the general solution at a base case, so multiplying it
+    /// with the constants gives f(n)
+    pub fn matrix(&self) -> &[Vec<f64>] {
+        &self.matrix
+    }
+
+    /// returns the constants solving the linear system
+    pub fn constants(&self) -> &[f64] {
+        &self.constants
+    }
+
+    /// returns the solution, the general solution with the constants filled in
+    pub fn solution(&self) -> &RecurrenceSolution {
+        &self.solution
+    }
+
+    /// returns the solution written with radicals, if solve_exact finds one
+    pub fn exact_solution(&self) -> Option<&ExactSolution> {
+        self.exact_solution.as_ref()
+    }
+
+    // returns the title and the formulas of each step
+    fn steps(&self, syntax: &Syntax) -> Vec<(&'static str, Vec<String>)> {
+        let mut steps = Vec::new();
+
+        let relation = if syntax.latex {
+            self.relation.to_latex(&syntax.latex_options)
+        } else {
+            self.relation.to_string()
+        };
+        steps.push(("Recurrence relation", vec![relation]));
+
+        let characteristic_polynomial = if syntax.latex {
+            self.characteristic_polynomial
+                .to_latex(&syntax.latex_options)
+        } else {
+            let options = FormatOptions {
+                variable: "x".to_string(),
+                ..syntax.text_options.clone()
+            };
+            self.characteristic_polynomial.format(&options)
+        };
+        steps.push((
+            "Characteristic equation",
+            vec![format!("{} = 0", characteristic_polynomial)],
+        ));
+
+        let roots = self
+            .roots
+            .iter()
+            .enumerate()
+            .map(|(k, (root, multiplicity))| {
+                let multiplicity = if syntax.latex {
+                    format!("\\quad (\\text{{multiplicity }} {})", multiplicity)
+                } else {
+                    format!(" (multiplicity {})", multiplicity)
+                };
+                format!(
+                    "{} = {}{}",
+                    syntax.subscript("x", k + 1),
+                    syntax.number(*root),
+                    multiplicity
+                )
+            })
+            .collect();
+        steps.push(("Roots of the characteristic equation", roots));
+
+        let mut general = Vec::new();
+        let mut k = 0;
+        for (root, multiplicity) in &self.roots {
+            let mut terms = Vec::new();
+            for i in 0..*multiplicity {
+                k += 1;
+                let monomial = match i {
+                    0 => String::new(),
+                    1 => "n".to_string(),
+                    _ => syntax.power("n", &i.to_string()),
+                };
+                terms.push((false, syntax.product(&[syntax.subscript("c", k), monomial])));
+            }
+            let mut polynomial = syntax.join_terms(&terms);
+            if *multiplicity > 1 {
+                polynomial = syntax.parenthesized(&polynomial);
+            }
+            general.push((
+                false,
+                syntax.product(&[polynomial, syntax.root_power(*root)]),
+            ));
+        }
+        steps.push((
+            "General solution",
+            vec![format!(
+                "{} = {}",
+                syntax.f("n"),
+                syntax.join_terms(&general)
+            )],
+        ));
+
+        let system = self
+            .matrix
+            .iter()
+            .zip(self.relation.base_cases())
+            .map(|(row, base_case)| {
+                let mut terms = Vec::new();
+                for (j, value) in row.iter().enumerate() {
+                    if *value == 0.0 {
+                        continue;
+                    }
+                    let magnitude = syntax.number(value.abs());
+                    let constant = syntax.subscript("c", j + 1);
+                    let term = if magnitude == syntax.number(1.0) {
+                        constant
+                    } else {
+                        syntax.product(&[magnitude, constant])
+                    };
+                    terms.push((*value < 0.0, term));
+                }
+                format!(
+                    "{} = {}",
+                    syntax.join_terms(&terms),
+                    syntax.number(*base_case)
+                )
+            })
+            .collect();
+        steps.push(("Linear system from the base cases", system));
+
+        let constants = self
+            .constants
+            .iter()
+            .enumerate()
+            .map(|(j, constant)| {
+                format!(
+                    "{} = {}",
+                    syntax.subscript("c", j + 1),
+                    syntax.number(*constant)
+                )
+            })
+            .collect();
+        steps.push(("Solution of the linear system", constants));
+
+        let mut closed_form = Vec::new();
+        if syntax.latex {
+            let options = LatexOptions {
+                closed_form: false,
+                ..syntax.latex_options.clone()
+            };
+            closed_form.push(self.solution.to_latex(&options));
+            if let Some(exact_solution) = &self.exact_solution {
+                closed_form.push(exact_solution.to_latex(&syntax.latex_options));
+            }
+        } else {
+            closed_form.push(self.solution.format(&syntax.text_options));
+            if let Some(exact_solution) = &self.exact_solution {
+                closed_form.push(exact_solution.to_string());
+            }
+        }
+        let closed_form = closed_form
+            .iter()
+            .map(|x| format!("{} = {}", syntax.f("n"), x))
+            .collect();
+        steps.push(("Closed form", closed_form));
+
+        for (_, formulas) in steps.iter_mut() {
+            if formulas.is_empty() {
+                formulas.push(syntax.none());
+            }
+        }
+        steps
+    }
+
+    /// returns the explanation written in the given format
+    pub fn render(&self, format: ExplanationFormat) -> String {
+        let syntax = Syntax::new(format == ExplanationFormat::Latex);
+        let mut sections = Vec::new();
+        for (i, (title, formulas)) in self.steps(&syntax).iter().enumerate() {
+            let section = match format {
+                ExplanationFormat::Text => {
+                    let mut section = format!("{}. {}", i + 1, title);
+                    for formula in formulas {
+                        section.push_str(&format!("\n   {}", formula));
+                    }
+                    section
+                }
+                ExplanationFormat::Markdown => {
+                    let mut section = format!("### {}. {}\n", i + 1, title);
+                    for formula in formulas {
+                        section.push_str(&format!("\n- `{}`", formula));
+                    }
+                    section
+                }
+                ExplanationFormat::Latex => {
+                    let mut section = format!("\\paragraph{{{}. {}}}", i + 1, title);
+                    for formula in formulas {
+                        section.push_str(&format!("\n\\[ {} \\]", formula));
+                    }
+                    section
+                }
+            };
+            sections.push(section);
+        }
+        sections.join("\n\n")
+    }
+}
+
+/// writes the explanation as plain text
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(ExplanationFormat::Text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let relation = RecurrenceRelation::new(vec![1.0, 3.0], vec![4.0, -4.0]);
+        let explanation = relation.explain();
+        assert_eq!(explanation.roots(), &[(2.0, 2)]);
+        assert_eq!(explanation.matrix(), &[vec![1.0, 0.0], vec![2.0, 2.0]]);
+        assert_eq!(
+            explanation.to_string(),
+            "1. Recurrence relation\n   f(n) = 4f(n-1) - 4f(n-2), f(0) = 1, f(1) = 3\n\n\
+             2. Characteristic equation\n   x^2 - 4.000x + 4.000 = 0\n\n\
+             3. Roots of the characteristic equation\n   x_1 = 2.000 (multiplicity 2)\n\n\
+             4. General solution\n   f(n) = (c_1 + c_2·n)·2.000^n\n\n\
+             5. Linear system from the base cases\n   c_1 = 1.000\n   2.000·c_1 + 2.000·c_2 = 3.000\n\n\
+             6. Solution of the linear system\n   c_1 = 1.000\n   c_2 = 0.500\n\n\
+             7. Closed form\n   f(n) = (0.500n + 1.000)·2.000^n\n   f(n) = 2^n + (1/2)n·2^n"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let explanation = fibonacci.explain();
+
+        let markdown = explanation.render(ExplanationFormat::Markdown);
+        assert!(markdown.starts_with(
+            "### 1. Recurrence relation\n\n- `f(n) = f(n-1) + f(n-2), f(0) = 0, f(1) = 1`\n\n"
+        ));
+        assert!(markdown.contains("- `f(n) = c_1·(-0.618)^n + c_2·1.618^n`"));
+
+        let latex = explanation.render(ExplanationFormat::Latex);
+        assert!(
+            latex.contains("\\paragraph{2. Characteristic equation}\n\\[ x^{2} - x - 1 = 0 \\]")
+        );
+        assert!(latex.ends_with(
+            "\\[ f_{n} = \\left(\\frac{1}{\\sqrt{5}}\\right)\\varphi^{n} \
+             - \\left(\\frac{1}{\\sqrt{5}}\\right)\\psi^{n} \\]"
+        ));
+
+        // the base cases of the linear system are written with the precision of the format
+        let third = RecurrenceRelation::new(vec![1.0 / 3.0], vec![2.0]).explain();
+        assert!(third
+            .to_string()
+            .contains("Linear system from the base cases\n   c_1 = 0.333\n"));
+        assert!(third
+            .render(ExplanationFormat::Latex)
+            .contains("the base cases}\n\\[ c_{1} = 0.333 \\]"));
+
+        let zero = RecurrenceRelation::new(vec![], vec![]).explain();
+        assert!(zero
+            .to_string()
+            .contains("3. Roots of the characteristic equation\n   none"));
+    }
+}
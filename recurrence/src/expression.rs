@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseExpressionError {
+    MissingEquals,
+    NoRecurrence,
+    MultipleRecurrence,
+    MultipleBaseCase,
+    MultipleParameter,
+    BaseCaseGap,
+    UnexpectedCharacter(char),
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    UnknownFunction(String),
+    ParseFloatError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EvaluationError {
+    /// f(n) referred to a term which is negative or not before n
+    IndexOutOfRange { n: usize, index: f64 },
+    /// the expression uses a name which is neither a parameter nor a constant
+    UnknownParameter(String),
+    /// computing the term would take more steps than allowed
+    StepLimitExceeded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    // the remainder with the sign of the divisor, so -1 mod 7 is 6
+    Modulo,
+    Power,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Exp,
+    Ln,
+    Sqrt,
+    Abs,
+    Floor,
+    Ceil,
+}
+
+// the syntax tree of the right side of a nonlinear recurrence
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum Expression {
+    Number(f64),
+    // the index n of the term being computed
+    Index,
+    // an earlier term of the sequence, at the index given by the expression rounded down
+    Term(Box<Expression>),
+    Parameter(String),
+    Negate(Box<Expression>),
+    Apply(Function, Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        let function = match name {
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            "tan" => Function::Tan,
+            "exp" => Function::Exp,
+            "ln" => Function::Ln,
+            "sqrt" => Function::Sqrt,
+            "abs" => Function::Abs,
+            "floor" => Function::Floor,
+            "ceil" => Function::Ceil,
+            _ => return None,
+        };
+        Some(function)
+    }
+
+    fn apply(self, x: f64) -> f64 {
+        match self {
+            Function::Sin => x.sin(),
+            Function::Cos => x.cos(),
+            Function::Tan => x.tan(),
+            Function::Exp => x.exp(),
+            Function::Ln => x.ln(),
+            Function::Sqrt => x.sqrt(),
+            Function::Abs => x.abs(),
+            Function::Floor => x.floor(),
+            Function::Ceil => x.ceil(),
+        }
+    }
+}
+
+impl Operator {
+    fn apply(self, x: f64, y: f64) -> f64 {
+        match self {
+            Operator::Add => x + y,
+            Operator::Subtract => x - y,
+            Operator::Multiply => x * y,
+            Operator::Divide => x / y,
+            Operator::Modulo => x - y * (x / y).floor(),
+            Operator::Power => x.powf(y),
+        }
+    }
+}
+
+impl Expression {
+    // evaluates the expression for the term n, looking up earlier terms with term
+    pub(crate) fn evaluate<F>(
+        &self,
+        n: usize,
+        parameters: &HashMap<String, f64>,
+        term: &mut F,
+    ) -> Result<f64, EvaluationError>
+    where
+        F: FnMut(f64) -> Result<f64, EvaluationError>,
+    {
+        let value = match self {
+            Expression::Number(x) => *x,
+            Expression::Index => n as f64,
+            Expression::Term(index) => {
+                let index = index.evaluate(n, parameters, term)?;
+                term(index)?
+            }
+            Expression::Parameter(name) => *parameters
+                .get(name)
+                .ok_or_else(|| EvaluationError::UnknownParameter(name.clone()))?,
+            Expression::Negate(x) => -x.evaluate(n, parameters, term)?,
+            Expression::Apply(function, x) => function.apply(x.evaluate(n, parameters, term)?),
+            Expression::Binary(operator, x, y) => operator.apply(
+                x.evaluate(n, parameters, term)?,
+                y.evaluate(n, parameters, term)?,
+            ),
+        };
+        Ok(value)
+    }
+
+    // returns whether the expression only uses the term f(n-1) and not n itself,
+    // so that it gives the map x(n) = g(x(n-1))
+    pub(crate) fn is_first_order(&self) -> bool {
+        match self {
+            Expression::Number(_) | Expression::Parameter(_) => true,
+            Expression::Index => false,
+            Expression::Term(index) => {
+                **index
+                    == Expression::Binary(
+                        Operator::Subtract,
+                        Box::new(Expression::Index),
+                        Box::new(Expression::Number(1.0)),
+                    )
+            }
+            Expression::Negate(x) | Expression::Apply(_, x) => x.is_first_order(),
+            Expression::Binary(_, x, y) => x.is_first_order() && y.is_first_order(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(char),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ParseExpressionError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // an exponent, as in 1e-3
+            if i + 1 < chars.len()
+                && (chars[i] == 'e' || chars[i] == 'E')
+                && (chars[i + 1].is_ascii_digit()
+                    || (chars[i + 1] == '-' || chars[i + 1] == '+')
+                        && chars.get(i + 2).is_some_and(|c| c.is_ascii_digit()))
+            {
+                i += 2;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let number: String = chars[start..i].iter().collect();
+            let number = number
+                .parse()
+                .map_err(|_| ParseExpressionError::ParseFloatError)?;
+            tokens.push(Token::Number(number));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Name(chars[start..i].iter().collect()));
+        } else if "+-*/%^()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        } else {
+            return Err(ParseExpressionError::UnexpectedCharacter(c));
+        }
+    }
+    Ok(tokens)
+}
+
+// a recursive descent parser, from the lowest precedence up:
+// mod, then + and -, then *, / and %, then unary minus, then ^ which groups to the right
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    // the name of the sequence, so f(n-1) is read as a term rather than a function
+    sequence: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ParseExpressionError> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or(ParseExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ParseExpressionError> {
+        match self.next()? {
+            Token::Symbol(c) if c == symbol => Ok(()),
+            token => Err(ParseExpressionError::UnexpectedToken(format!(
+                "{:?}",
+                token
+            ))),
+        }
+    }
+
+    fn modulo(&mut self) -> Result<Expression, ParseExpressionError> {
+        let mut left = self.sum()?;
+        while self.peek() == Some(&Token::Name("mod".to_string())) {
+            self.position += 1;
+            let right = self.sum()?;
+            left = Expression::Binary(Operator::Modulo, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn sum(&mut self) -> Result<Expression, ParseExpressionError> {
+        let mut left = self.product()?;
+        loop {
+            let operator = if self.eat('+') {
+                Operator::Add
+            } else if self.eat('-') {
+                Operator::Subtract
+            } else {
+                return Ok(left);
+            };
+            let right = self.product()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn product(&mut self) -> Result<Expression, ParseExpressionError> {
+        let mut left = self.unary()?;
+        loop {
+            let operator = if self.eat('*') {
+                Operator::Multiply
+            } else if self.eat('/') {
+                Operator::Divide
+            } else if self.eat('%') {
+                Operator::Modulo
+            } else {
+                return Ok(left);
+            };
+            let right = self.unary()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expression, ParseExpressionError> {
+        if self.eat('-') {
+            Ok(Expression::Negate(Box::new(self.unary()?)))
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Expression, ParseExpressionError> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            let exponent = self.unary()?;
+            return Ok(Expression::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expression, ParseExpressionError> {
+        match self.next()? {
+            Token::Number(x) => Ok(Expression::Number(x)),
+            Token::Symbol('(') => {
+                let expression = self.modulo()?;
+                self.expect(')')?;
+                Ok(expression)
+            }
+            Token::Name(name) if self.eat('(') => {
+                let argument = self.modulo()?;
+                self.expect(')')?;
+                if name == self.sequence {
+                    Ok(Expression::Term(Box::new(argument)))
+                } else {
+                    let function = Function::from_name(&name)
+                        .ok_or(ParseExpressionError::UnknownFunction(name))?;
+                    Ok(Expression::Apply(function, Box::new(argument)))
+                }
+            }
+            Token::Name(name) => Ok(match name.as_str() {
+                "n" => Expression::Index,
+                "pi" => Expression::Number(std::f64::consts::PI),
+                "e" => Expression::Number(std::f64::consts::E),
+                _ => Expression::Parameter(name),
+            }),
+            token => Err(ParseExpressionError::UnexpectedToken(format!(
+                "{:?}",
+                token
+            ))),
+        }
+    }
+}
+
+// parses an expression in which calls to the sequence are terms
+pub(crate) fn parse_expression(
+    s: &str,
+    sequence: &str,
+) -> Result<Expression, ParseExpressionError> {
+    let tokens = tokenize(s)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        sequence,
+    };
+    let expression = parser.modulo()?;
+    match parser.peek() {
+        None => Ok(expression),
+        Some(token) => Err(ParseExpressionError::UnexpectedToken(format!(
+            "{:?}",
+            token
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(s: &str, n: usize) -> f64 {
+        let parameters = vec![("r".to_string(), 2.0)].into_iter().collect();
+        // the earlier term at index i is i + 10
+        let mut term = |i: f64| Ok(i + 10.0);
+        parse_expression(s, "f")
+            .unwrap()
+            .evaluate(n, &parameters, &mut term)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        assert_eq!(evaluate("1 + 2 * 3", 0), 7.0);
+        assert_eq!(evaluate("2^3^2", 0), 512.0);
+        assert_eq!(evaluate("-2^2", 0), -4.0);
+        assert_eq!(evaluate("r*f(n-1)*(1-f(n-1))", 5), 2.0 * 14.0 * -13.0);
+        assert_eq!(evaluate("f(n-1) + f(n-2) mod 7", 3), 23.0 % 7.0);
+        assert_eq!(evaluate("-1 mod 7", 0), 6.0);
+        assert_eq!(evaluate("7 % 4 + 1", 0), 4.0);
+        assert_eq!(evaluate("f(floor(n/2)) + 1.5e1", 5), 27.0);
+        assert_eq!(evaluate("sqrt(abs(-16)) + cos(0)", 0), 5.0);
+
+        assert_eq!(
+            parse_expression("g(n-1)", "f"),
+            Err(ParseExpressionError::UnknownFunction("g".to_string()))
+        );
+        assert_eq!(
+            parse_expression("1 + ", "f"),
+            Err(ParseExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            parse_expression("1 # 2", "f"),
+            Err(ParseExpressionError::UnexpectedCharacter('#'))
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::recurrence_relation::RecurrenceRelation;
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+
+/// a linear recurrence driven by an input signal, y(n) = Σ a_i y(n-i) + Σ b_j x(n-j), with
+/// feedback coefficients a_1, a_2, ... and feedforward coefficients b_0, b_1, ...
+/// the filter keeps the recent inputs and outputs, so a signal can be processed in pieces
+/// and it starts at rest, with every earlier input and output zero
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Filter {
+    feedback: Vec<f64>,
+    feedforward: Vec<f64>,
+    // the most recent first
+    outputs: VecDeque<f64>,
+    inputs: VecDeque<f64>,
+}
+
+impl Filter {
+    /// creates a filter at rest with feedback coefficients a_1, a_2, ... and
+    /// feedforward coefficients b_0, b_1, ...
+    pub fn new(feedback: Vec<f64>, feedforward: Vec<f64>) -> Filter {
+        Filter {
+            outputs: vec![0.0; feedback.len()].into(),
+            inputs: vec![0.0; feedforward.len()].into(),
+            feedback,
+            feedforward,
+        }
+    }
+
+    /// returns the feedback coefficients a_1, a_2, ...
+    pub fn feedback(&self) -> &[f64] {
+        &self.feedback
+    }
+
+    /// returns the feedforward coefficients b_0, b_1, ...
+    pub fn feedforward(&self) -> &[f64] {
+        &self.feedforward
+    }
+
+    /// returns the filter to rest, forgetting every earlier input and output
+    pub fn reset(&mut self) {
+        self.outputs.iter_mut().for_each(|y| *y = 0.0);
+        self.inputs.iter_mut().for_each(|x| *x = 0.0);
+    }
+
+    // adds an input and the output it gave to the history
+    fn record(&mut self, x: f64, y: f64) {
+        self.outputs.push_front(y);
+        self.outputs.truncate(self.feedback.len());
+        self.inputs.push_front(x);
+        self.inputs.truncate(self.feedforward.len());
+    }
+
+    /// feeds the next input x(n) through the filter and returns the output y(n)
+    pub fn step(&mut self, x: f64) -> f64 {
+        let mut y = 0.0;
+        for (coefficient, output) in self.feedback.iter().zip(&self.outputs) {
+            y += coefficient * output;
+        }
+        // b_0 multiplies x itself and b_j the input j steps back
+        let mut earlier_inputs = std::iter::once(&x).chain(&self.inputs);
+        for coefficient in &self.feedforward {
+            y += coefficient * earlier_inputs.next().unwrap();
+        }
+        self.record(x, y);
+        y
+    }
+
+    /// feeds the inputs through the filter in order and returns the outputs, continuing from
+    /// where the last call left off
+    pub fn process<I>(&mut self, input: I) -> Vec<f64>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<f64>,
+    {
+        input.into_iter().map(|x| self.step(*x.borrow())).collect()
+    }
+
+    /// returns the first n outputs for the unit impulse 1, 0, 0, ... starting from rest
+    pub fn impulse_response(&self, n: usize) -> Vec<f64> {
+        let mut filter = Filter::new(self.feedback.clone(), self.feedforward.clone());
+        (0..n)
+            .map(|i| filter.step(if i == 0 { 1.0 } else { 0.0 }))
+            .collect()
+    }
+
+    /// returns the first n outputs for the unit step 1, 1, 1, ... starting from rest
+    pub fn step_response(&self, n: usize) -> Vec<f64> {
+        let mut filter = Filter::new(self.feedback.clone(), self.feedforward.clone());
+        (0..n).map(|_| filter.step(1.0)).collect()
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns a filter at rest with the recurrence coefficients as feedback
+    /// and the given feedforward coefficients b_0, b_1, ...
+    pub fn filter(&self, feedforward: Vec<f64>) -> Filter {
+        Filter::new(self.recurrence_coefficients().to_vec(), feedforward)
+    }
+
+    /// returns f(n) = Σ a_i f(n-i) + Σ b_j x(n-j) for each input x(n), with the base cases as the
+    /// first terms and x(n) taken as zero for n < 0
+    /// with an input of all zeros this is the same as get_terms
+    pub fn drive<I>(&self, feedforward: &[f64], input: I) -> Vec<f64>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<f64>,
+    {
+        let mut filter = self.filter(feedforward.to_vec());
+        let mut terms = Vec::new();
+        for (n, x) in input.into_iter().enumerate() {
+            let x = *x.borrow();
+            if n < self.degree() {
+                filter.record(x, self.base_cases()[n]);
+                terms.push(self.base_cases()[n]);
+            } else {
+                terms.push(filter.step(x));
+            }
+        }
+        terms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        // y(n) = 0.5y(n-1) + x(n) + x(n-1)
+        let mut filter = Filter::new(vec![0.5], vec![1.0, 1.0]);
+        assert_eq!(filter.impulse_response(4), vec![1.0, 1.5, 0.75, 0.375]);
+        assert_eq!(filter.step_response(4), vec![1.0, 2.5, 3.25, 3.625]);
+
+        // processing in pieces gives the same outputs as all at once
+        let input = [1.0, -2.0, 3.0, 0.5, 4.0];
+        let whole = filter.process(input.iter());
+        filter.reset();
+        let mut pieces = filter.process(&input[..2]);
+        pieces.extend(filter.process(input[2..].iter().copied()));
+        assert_eq!(whole, pieces);
+
+        // a moving average has no feedback
+        let mut average = Filter::new(vec![], vec![0.5, 0.5]);
+        assert_eq!(average.process(vec![2.0, 4.0, 6.0]), vec![1.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_drive() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(
+            fibonacci.drive(&[1.0], vec![0.0; 8]),
+            fibonacci.get_terms(8)
+        );
+
+        // f(n) = f(n-1) + x(n) gives the partial sums of the input after f(0)
+        let relation = RecurrenceRelation::new(vec![10.0], vec![1.0]);
+        assert_eq!(
+            relation.drive(&[1.0], [5.0, 1.0, 2.0, 3.0]),
+            vec![10.0, 11.0, 13.0, 16.0]
+        );
+        // the impulse response of the filter is the fibonacci sequence from f(1)
+        assert_eq!(
+            fibonacci.filter(vec![1.0]).impulse_response(6),
+            vec![1.0, 1.0, 2.0, 3.0, 5.0, 8.0]
+        );
+    }
+}
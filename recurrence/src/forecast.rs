@@ -0,0 +1,213 @@
+use crate::autoregressive::ArModel;
+use crate::recurrence_relation::RecurrenceRelation;
+
+/// forecasts of the next values of a series with prediction intervals, from a recurrence
+/// x(n) = Σ φ_i x(n-i) + e(n) driven by noise e(n) of variance σ²
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Forecast {
+    mean: Vec<f64>,
+    standard_errors: Vec<f64>,
+    level: f64,
+}
+
+// returns the quantile of the standard normal distribution at p, using acklam's rational
+// approximation, which has a relative error below 1.2e-9
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    let polynomial =
+        |coefficients: &[f64], x: f64| coefficients.iter().fold(0.0, |res, c| res * x + c);
+
+    // the tails use a rational function of sqrt(-2 ln p) and the middle one of p - 1/2
+    let tail = |p: f64| {
+        let q = (-2.0 * p.ln()).sqrt();
+        polynomial(&C, q) / (polynomial(&D, q) * q + 1.0)
+    };
+    if p < 0.02425 {
+        tail(p)
+    } else if p > 1.0 - 0.02425 {
+        -tail(1.0 - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        polynomial(&A, r) * q / (polynomial(&B, r) * r + 1.0)
+    }
+}
+
+impl Forecast {
+    /// returns the point forecasts of the next values
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// returns the standard error of each forecast, σ sqrt(ψ_0² + ... + ψ_(h-1)²) h steps ahead
+    pub fn standard_errors(&self) -> &[f64] {
+        &self.standard_errors
+    }
+
+    /// returns the probability that each prediction interval is meant to cover
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    // returns the half width of the intervals in standard errors
+    fn critical_value(&self) -> f64 {
+        normal_quantile(0.5 + self.level / 2.0)
+    }
+
+    /// returns the lower ends of the prediction intervals
+    pub fn lower(&self) -> Vec<f64> {
+        let z = self.critical_value();
+        self.mean
+            .iter()
+            .zip(&self.standard_errors)
+            .map(|(mean, error)| mean - z * error)
+            .collect()
+    }
+
+    /// returns the upper ends of the prediction intervals
+    pub fn upper(&self) -> Vec<f64> {
+        let z = self.critical_value();
+        self.mean
+            .iter()
+            .zip(&self.standard_errors)
+            .map(|(mean, error)| mean + z * error)
+            .collect()
+    }
+
+    /// returns the forecasts as csv with the columns step, mean, lower and upper,
+    /// where step h is the forecast h steps ahead
+    pub fn to_csv(&self) -> String {
+        let mut csv = "step,mean,lower,upper\n".to_string();
+        let lower = self.lower();
+        let upper = self.upper();
+        for (i, mean) in self.mean.iter().enumerate() {
+            csv.push_str(&format!("{},{},{},{}\n", i + 1, mean, lower[i], upper[i]));
+        }
+        csv
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns forecasts of the horizon values after the history, with prediction intervals
+    /// covering the given probability under gaussian noise of the given variance
+    /// the forecast errors come from the MA(∞) representation x(n) = Σ ψ_j e(n-j), whose weights
+    /// ψ_j are the impulse response of the recurrence
+    /// returns None if the history is shorter than the degree or the level is not in (0, 1)
+    pub fn forecast(
+        &self,
+        history: &[f64],
+        noise_variance: f64,
+        horizon: usize,
+        level: f64,
+    ) -> Option<Forecast> {
+        let degree = self.degree();
+        if history.len() < degree || !(level > 0.0 && level < 1.0) {
+            return None;
+        }
+
+        // the expected future noise is zero, so the forecasts follow the recurrence itself
+        let mut values = history[history.len() - degree..].to_vec();
+        for _ in 0..horizon {
+            let next = values
+                .iter()
+                .rev()
+                .zip(self.recurrence_coefficients())
+                .map(|(value, coefficient)| value * coefficient)
+                .sum();
+            values.push(next);
+        }
+
+        let mut sum_of_squares = 0.0;
+        let standard_errors = self
+            .filter(vec![1.0])
+            .impulse_response(horizon)
+            .iter()
+            .map(|psi| {
+                sum_of_squares += psi * psi;
+                (noise_variance * sum_of_squares).sqrt()
+            })
+            .collect();
+
+        Some(Forecast {
+            mean: values[degree..].to_vec(),
+            standard_errors,
+            level,
+        })
+    }
+}
+
+impl ArModel {
+    /// returns forecasts of the horizon values after the history using the fitted noise variance
+    pub fn forecast(&self, history: &[f64], horizon: usize, level: f64) -> Option<Forecast> {
+        self.relation()
+            .forecast(history, self.noise_variance(), horizon, level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_quantile() {
+        assert!(normal_quantile(0.5).abs() < 1e-9);
+        assert!((normal_quantile(0.975) - 1.959_963_984_540_054).abs() < 1e-8);
+        assert!((normal_quantile(0.01) + 2.326_347_874_040_841).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_forecast() {
+        // x(n) = 0.5x(n-1) + e(n) with σ² = 4, so ψ_j = 0.5^j
+        let relation = RecurrenceRelation::new(vec![0.0], vec![0.5]);
+        let forecast = relation.forecast(&[3.0, 8.0], 4.0, 3, 0.95).unwrap();
+        assert_eq!(forecast.mean(), &[4.0, 2.0, 1.0]);
+        let expected = [2.0, 2.0 * 1.25f64.sqrt(), 2.0 * 1.3125f64.sqrt()];
+        for (error, expected) in forecast.standard_errors().iter().zip(&expected) {
+            assert!((error - expected).abs() < 1e-12);
+        }
+        assert!((forecast.lower()[0] - (4.0 - 1.959_963_984_540_054 * 2.0)).abs() < 1e-7);
+        assert!((forecast.upper()[0] - (4.0 + 1.959_963_984_540_054 * 2.0)).abs() < 1e-7);
+        assert_eq!(forecast.to_csv().lines().count(), 4);
+
+        // the intervals of a random walk widen as sqrt(h)
+        let walk = RecurrenceRelation::new(vec![0.0], vec![1.0]);
+        let forecast = walk.forecast(&[5.0], 1.0, 4, 0.5).unwrap();
+        assert_eq!(forecast.mean(), &[5.0; 4]);
+        assert_eq!(
+            forecast.standard_errors(),
+            &[1.0, 2f64.sqrt(), 3f64.sqrt(), 2.0]
+        );
+
+        assert_eq!(relation.forecast(&[], 1.0, 3, 0.95), None);
+        assert_eq!(relation.forecast(&[1.0], 1.0, 3, 1.0), None);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::latex::{self, LatexOptions};
+use crate::polynomial::Polynomial;
+use crate::recurrence_solution::RecurrenceSolution;
+
+/// options controlling how polynomials and solutions are written as text
+/// Display uses the defaults, with the precision taken from the format string if given,
+/// so format!("{:.6}", solution) writes six decimals
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatOptions {
+    /// the number of digits after the decimal point
+    pub precision: usize,
+    /// writes numbers in scientific notation, for example 1.618e0
+    pub scientific: bool,
+    /// terms whose coefficient is at most this in absolute value are left out, except in solutions
+    /// where the root has modulus at least one, since those terms do not decay
+    /// terms with a zero coefficient are always left out
+    pub tolerance: f64,
+    /// writes 1.000n as n
+    pub omit_unit_coefficients: bool,
+    /// the variable of the polynomials
+    pub variable: String,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            precision: 3,
+            scientific: false,
+            tolerance: 1e-10,
+            omit_unit_coefficients: true,
+            variable: "n".to_string(),
+        }
+    }
+}
+
+impl FormatOptions {
+    // formats a number with the precision and notation of the options
+    pub(crate) fn number(&self, x: f64) -> String {
+        if self.scientific {
+            format!("{:.*e}", self.precision, x)
+        } else {
+            format!("{:.*}", self.precision, x)
+        }
+    }
+
+    fn is_negligible(&self, x: f64) -> bool {
+        x == 0.0 || x.abs() <= self.tolerance
+    }
+
+    // whether the number is written the same as 1
+    fn is_unit(&self, x: f64) -> bool {
+        self.number(x) == self.number(1.0)
+    }
+}
+
+// formats a float with at most the given number of decimals, dropping trailing zeros
+pub(crate) fn number(x: f64, precision: usize) -> String {
+    let mut res = format!("{:.*}", precision, x);
+    if res.contains('.') {
+        res = res.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    if res == "-0" {
+        res = "0".to_string();
+    }
+    res
+}
+
+// the output syntax of the text and latex writers, so both write terms and signs the same way
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Notation<'a> {
+    Text(&'a FormatOptions),
+    Latex(&'a LatexOptions),
+}
+
+impl Notation<'_> {
+    pub(crate) fn number(&self, x: f64) -> String {
+        match self {
+            Notation::Text(options) => options.number(x),
+            Notation::Latex(options) => number(x, options.precision),
+        }
+    }
+
+    // whether a coefficient is left out, text uses the tolerance and latex drops what rounds to 0
+    fn is_negligible(&self, x: f64) -> bool {
+        match self {
+            Notation::Text(options) => options.is_negligible(x),
+            Notation::Latex(_) => self.number(x.abs()) == "0",
+        }
+    }
+
+    fn omits_unit_coefficients(&self) -> bool {
+        match self {
+            Notation::Text(options) => options.omit_unit_coefficients,
+            Notation::Latex(_) => true,
+        }
+    }
+
+    fn merges_signs(&self) -> bool {
+        match self {
+            Notation::Text(_) => true,
+            Notation::Latex(options) => options.merge_signs,
+        }
+    }
+
+    pub(crate) fn parenthesized(&self, x: &str) -> String {
+        match self {
+            Notation::Text(_) => format!("({})", x),
+            Notation::Latex(_) => format!("\\left({}\\right)", x),
+        }
+    }
+
+    pub(crate) fn power(&self, base: &str, exponent: &str) -> String {
+        match self {
+            Notation::Text(_) => format!("{}^{}", base, exponent),
+            Notation::Latex(_) => latex::power(base, exponent),
+        }
+    }
+
+    // joins terms, each given as whether it is negative and its magnitude, as a - b + c
+    // without merged signs a negative term is added as + (-b)
+    pub(crate) fn join_terms(&self, terms: &[(bool, String)]) -> String {
+        let mut res = String::new();
+        for (negative, magnitude) in terms {
+            if res.is_empty() {
+                if *negative {
+                    res.push('-');
+                }
+                res.push_str(magnitude);
+            } else if !negative {
+                res.push_str(&format!(" + {}", magnitude));
+            } else if self.merges_signs() {
+                res.push_str(&format!(" - {}", magnitude));
+            } else {
+                let negated = self.parenthesized(&format!("-{}", magnitude));
+                res.push_str(&format!(" + {}", negated));
+            }
+        }
+        if res.is_empty() {
+            res.push('0');
+        }
+        res
+    }
+
+    // the terms of a polynomial from the highest power down, leaving out zero terms
+    // negligible terms are left out too when decaying, which is not the case for the coefficients
+    // of r^n with |r| >= 1, since any nonzero such term eventually dominates
+    pub(crate) fn polynomial_terms(
+        &self,
+        coefficients: &[f64],
+        variable: &str,
+        decaying: bool,
+    ) -> Vec<(bool, String)> {
+        let mut terms = Vec::new();
+        for (pow, coefficient) in coefficients.iter().enumerate().rev() {
+            if *coefficient == 0.0 || decaying && self.is_negligible(*coefficient) {
+                continue;
+            }
+            let monomial = match pow {
+                0 => String::new(),
+                1 => variable.to_string(),
+                _ => self.power(variable, &pow.to_string()),
+            };
+            let magnitude = self.number(coefficient.abs());
+            let term = if pow > 0 && self.omits_unit_coefficients() && magnitude == self.number(1.0)
+            {
+                monomial
+            } else {
+                format!("{}{}", magnitude, monomial)
+            };
+            terms.push((*coefficient < 0.0, term));
+        }
+        terms
+    }
+}
+
+impl Polynomial {
+    /// returns the polynomial as text with the given options, for example n^2 - 2.500n + 1.000
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let notation = Notation::Text(options);
+        notation.join_terms(&notation.polynomial_terms(
+            self.coefficients(),
+            &options.variable,
+            true,
+        ))
+    }
+}
+
+impl RecurrenceSolution {
+    /// returns the solution as text with the given options,
+    /// for example 0.447·1.618^n - 0.447·(-0.618)^n
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let notation = Notation::Text(options);
+        let mut terms = Vec::new();
+        for (polynomial, root) in self.terms() {
+            let root_power = if options.is_unit(*root) {
+                None
+            } else if *root < 0.0 {
+                Some(format!("({})^{}", options.number(*root), options.variable))
+            } else {
+                Some(format!("{}^{}", options.number(*root), options.variable))
+            };
+
+            let mut polynomial_terms = notation.polynomial_terms(
+                polynomial.coefficients(),
+                &options.variable,
+                root.abs() < 1.0,
+            );
+            let term = match (polynomial_terms.len(), root_power) {
+                (0, _) => continue,
+                (1, Some(root_power)) => {
+                    let (negative, magnitude) = polynomial_terms.remove(0);
+                    // a lone constant term of one is left out
+                    if options.omit_unit_coefficients && magnitude == options.number(1.0) {
+                        (negative, root_power)
+                    } else {
+                        (negative, format!("{}·{}", magnitude, root_power))
+                    }
+                }
+                (_, Some(root_power)) => (
+                    false,
+                    format!(
+                        "({})·{}",
+                        notation.join_terms(&polynomial_terms),
+                        root_power
+                    ),
+                ),
+                (_, None) => {
+                    terms.extend(polynomial_terms);
+                    continue;
+                }
+            };
+            terms.push(term);
+        }
+        notation.join_terms(&terms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_polynomial() {
+        let polynomial = Polynomial::new(vec![-1.0, 0.0, -1.0, 1.0]);
+        assert_eq!(polynomial.to_string(), "n^3 - n^2 - 1.000");
+        assert_eq!(format!("{:.1}", polynomial), "n^3 - n^2 - 1.0");
+
+        let options = FormatOptions {
+            precision: 2,
+            scientific: true,
+            tolerance: 1e-3,
+            omit_unit_coefficients: false,
+            variable: "x".to_string(),
+        };
+        let polynomial = Polynomial::new(vec![1e-6, -12345.0, 1.0]);
+        assert_eq!(polynomial.format(&options), "1.00e0x^2 - 1.23e4x");
+    }
+
+    #[test]
+    fn test_notation() {
+        let terms = vec![
+            (true, "2".to_string()),
+            (false, "n".to_string()),
+            (true, "1".to_string()),
+        ];
+        let text = FormatOptions::default();
+        assert_eq!(Notation::Text(&text).join_terms(&terms), "-2 + n - 1");
+        let mut latex = LatexOptions::default();
+        assert_eq!(Notation::Latex(&latex).join_terms(&terms), "-2 + n - 1");
+        latex.merge_signs = false;
+        assert_eq!(
+            Notation::Latex(&latex).join_terms(&terms),
+            "-2 + n + \\left(-1\\right)"
+        );
+        assert_eq!(Notation::Text(&text).join_terms(&[]), "0");
+
+        let coefficients = [-1.0, 0.0, 2.5, 1.0];
+        assert_eq!(
+            Notation::Text(&text).polynomial_terms(&coefficients, "n", true),
+            vec![
+                (false, "n^3".to_string()),
+                (false, "2.500n^2".to_string()),
+                (true, "1.000".to_string())
+            ]
+        );
+        assert_eq!(
+            Notation::Latex(&latex).polynomial_terms(&coefficients, "x", true),
+            vec![
+                (false, "x^{3}".to_string()),
+                (false, "2.5x^{2}".to_string()),
+                (true, "1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_solution() {
+        let fibonacci = RecurrenceSolution::new(vec![
+            (Polynomial::new(vec![0.4472135955]), 1.618_033_988_749_895),
+            (
+                Polynomial::new(vec![-0.4472135955]),
+                -0.618_033_988_749_894_9,
+            ),
+        ]);
+        assert_eq!(fibonacci.to_string(), "0.447·1.618^n - 0.447·(-0.618)^n");
+        assert_eq!(
+            format!("{:.6}", fibonacci),
+            "0.447214·1.618034^n - 0.447214·(-0.618034)^n"
+        );
+
+        // (n + 1)2^n + 3 - 2n
+        let solution = RecurrenceSolution::new(vec![
+            (Polynomial::new(vec![1.0, 1.0]), 2.0),
+            (Polynomial::new(vec![3.0, -2.0]), 1.0),
+            (Polynomial::new(vec![1.0]), 3.0),
+            (Polynomial::new(vec![0.0]), 5.0),
+        ]);
+        assert_eq!(
+            solution.to_string(),
+            "(n + 1.000)·2.000^n - 2.000n + 3.000 + 3.000^n"
+        );
+
+        // a small coefficient is only negligible when its root decays
+        let solution = RecurrenceSolution::new(vec![
+            (Polynomial::new(vec![2e-17]), 1e17),
+            (Polynomial::new(vec![1e-12]), 0.5),
+            (Polynomial::new(vec![1.0]), -1e-17),
+        ]);
+        let options = FormatOptions {
+            precision: 1,
+            scientific: true,
+            ..FormatOptions::default()
+        };
+        assert_eq!(solution.format(&options), "2.0e-17·1.0e17^n + (-1.0e-17)^n");
+        let latex = solution.to_latex(&LatexOptions {
+            closed_form: false,
+            ..LatexOptions::default()
+        });
+        // latex keeps the dominant term too, even though its coefficient rounds to 0
+        assert_eq!(
+            latex,
+            "0 \\cdot 100000000000000000^{n} + \\left(0\\right)^{n}"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::algebraic::Algebraic;
+use crate::rational_polynomial::Rational;
+use num_traits::Zero;
+
+// relative error allowed between a float and the closed form it is recognised as
+const TOLERANCE: f64 = 1e-10;
+
+// largest denominator tried when recognising a float as a rational
+const MAX_DENOMINATOR: i128 = 1000;
+
+// largest coefficient allowed in an integer relation
+const MAX_RELATION_COEFFICIENT: f64 = 1000.0;
+
+fn close(x: f64, y: f64) -> bool {
+    (x - y).abs() <= TOLERANCE * x.abs().max(1.0)
+}
+
+// returns the first convergent of the continued fraction of x which is within the tolerance
+fn recognise_rational(x: f64) -> Option<Rational> {
+    if !x.is_finite() || x.abs() > 1e12 {
+        return None;
+    }
+    let (mut p0, mut q0, mut p1, mut q1) = (0i128, 1i128, 1i128, 0i128);
+    let mut remainder = x;
+    loop {
+        let a = remainder.floor();
+        let p2 = a as i128 * p1 + p0;
+        let q2 = a as i128 * q1 + q0;
+        if q2 > MAX_DENOMINATOR {
+            return None;
+        }
+        if close(x, p2 as f64 / q2 as f64) {
+            return Some(Rational::new(p2, q2));
+        }
+        let fractional = remainder - a;
+        if fractional == 0.0 {
+            return None;
+        }
+        remainder = 1.0 / fractional;
+        p0 = p1;
+        q0 = q1;
+        p1 = p2;
+        q1 = q2;
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// returns the gram-schmidt orthogonalisation of the basis and its coefficients
+fn gram_schmidt(basis: &[Vec<f64>]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let n = basis.len();
+    let mut orthogonal: Vec<Vec<f64>> = Vec::new();
+    let mut mu = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        let mut v = basis[i].clone();
+        for j in 0..i {
+            mu[i][j] = dot(&basis[i], &orthogonal[j]) / dot(&orthogonal[j], &orthogonal[j]);
+            for (x, y) in v.iter_mut().zip(&orthogonal[j]) {
+                *x -= mu[i][j] * y;
+            }
+        }
+        orthogonal.push(v);
+    }
+    (orthogonal, mu)
+}
+
+// reduces the basis in place with the lenstra-lenstra-lovász algorithm
+fn lll_reduce(basis: &mut [Vec<f64>]) {
+    let delta = 0.75;
+    let mut k = 1;
+    while k < basis.len() {
+        for j in (0..k).rev() {
+            let q = gram_schmidt(basis).1[k][j].round();
+            if q != 0.0 {
+                let row = basis[j].clone();
+                for (x, y) in basis[k].iter_mut().zip(row) {
+                    *x -= q * y;
+                }
+            }
+        }
+        let (orthogonal, mu) = gram_schmidt(basis);
+        let lovasz =
+            (delta - mu[k][k - 1] * mu[k][k - 1]) * dot(&orthogonal[k - 1], &orthogonal[k - 1]);
+        if dot(&orthogonal[k], &orthogonal[k]) >= lovasz {
+            k += 1;
+        } else {
+            basis.swap(k, k - 1);
+            k = (k - 1).max(1);
+        }
+    }
+}
+
+/// searches for small integers c, not all zero, with c_0 v_0 + c_1 v_1 + ... = 0 using lll
+/// returns None if there is no such relation with coefficients of at most a thousand
+pub fn integer_relation(values: &[f64]) -> Option<Vec<i64>> {
+    let n = values.len();
+    let scale = 1e10 / values.iter().fold(1.0_f64, |acc, v| acc.max(v.abs()));
+    let mut basis: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            let mut row = vec![0.0; n + 1];
+            row[i] = 1.0;
+            row[n] = scale * values[i];
+            row
+        })
+        .collect();
+    lll_reduce(&mut basis);
+
+    basis
+        .iter()
+        .map(|row| row[..n].iter().map(|c| c.round()).collect::<Vec<f64>>())
+        .filter(|coefficients| {
+            let largest = coefficients.iter().fold(0.0_f64, |acc, c| acc.max(c.abs()));
+            let residual = dot(coefficients, values);
+            let size: f64 = coefficients
+                .iter()
+                .zip(values)
+                .map(|(c, v)| (c * v).abs())
+                .sum();
+            largest > 0.0
+                && largest <= MAX_RELATION_COEFFICIENT
+                && residual.abs() <= TOLERANCE * size.max(1.0)
+        })
+        .min_by(|a, b| {
+            let norm = |c: &Vec<f64>| dot(c, c);
+            norm(a).partial_cmp(&norm(b)).unwrap()
+        })
+        .map(|coefficients| coefficients.iter().map(|c| *c as i64).collect())
+}
+
+/// recognises a float as a closed-form number
+/// tries a rational with a small denominator, then a quadratic irrational (a + b√d)/c,
+/// then the cube root of a rational
+pub fn identify_constant(x: f64) -> Option<Algebraic> {
+    if let Some(r) = recognise_rational(x) {
+        return Some(Algebraic::Rational(r));
+    }
+
+    // a + bx + cx^2 = 0
+    if let Some(relation) = integer_relation(&[1.0, x, x * x]) {
+        let (a, b, c) = (
+            relation[0] as i128,
+            relation[1] as i128,
+            relation[2] as i128,
+        );
+        if c != 0 {
+            let shift = Rational::new(-b, 2 * c);
+            let candidates = [
+                Algebraic::surd(shift, Rational::new(1, 2 * c), b * b - 4 * a * c),
+                Algebraic::surd(shift, Rational::new(-1, 2 * c), b * b - 4 * a * c),
+            ];
+            if let Some(candidate) = candidates.iter().find(|y| {
+                let value = y.evaluate();
+                value.im == 0.0 && close(x, value.re)
+            }) {
+                return Some(candidate.clone());
+            }
+        }
+    }
+
+    if let Some(cube) = recognise_rational(x * x * x) {
+        if !cube.is_zero() {
+            let candidate = Algebraic::root(Algebraic::Rational(cube), 3);
+            if close(x, candidate.evaluate().re) {
+                return Some(candidate);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_relation() {
+        let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        let relation = integer_relation(&[1.0, phi, phi * phi]).unwrap();
+        assert!(relation == vec![1, 1, -1] || relation == vec![-1, -1, 1]);
+        assert_eq!(integer_relation(&[1.0, std::f64::consts::PI]), None);
+    }
+
+    #[test]
+    fn test_identify_constant() {
+        assert_eq!(
+            identify_constant(0.333_333_333_333).unwrap().to_string(),
+            "1/3"
+        );
+        assert_eq!(
+            identify_constant(-2.000_000_000_01).unwrap().to_string(),
+            "-2"
+        );
+        assert_eq!(
+            identify_constant(0.447_213_595_5).unwrap().to_string(),
+            "1/√5"
+        );
+        assert_eq!(
+            identify_constant(-0.618_033_988_749_894_9)
+                .unwrap()
+                .to_string(),
+            "(1-√5)/2"
+        );
+        assert_eq!(
+            identify_constant(2.0_f64.powf(1.0 / 3.0))
+                .unwrap()
+                .to_string(),
+            "∛2"
+        );
+        assert_eq!(identify_constant(std::f64::consts::E), None);
+        assert_eq!(identify_constant(std::f64::consts::PI.sqrt()), None);
+        assert_eq!(identify_constant(std::f64::consts::LN_2), None);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::algebraic::Algebraic;
+use crate::exact_solution::ExactSolution;
+use crate::format::{number, Notation};
+use crate::polynomial::Polynomial;
+use crate::rational_polynomial::{gcd, Rational};
+use crate::recurrence_relation::RecurrenceRelation;
+use crate::recurrence_solution::RecurrenceSolution;
+use num_traits::{Signed, Zero};
+
+/// options controlling how numbers and signs are written in latex
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatexOptions {
+    /// the number of digits after the decimal point for numbers which are not integers
+    pub precision: usize,
+    /// writes a + (-b) as a - b
+    pub merge_signs: bool,
+    /// writes solutions with radicals when their constants can be identified
+    pub closed_form: bool,
+    /// writes (1+√5)/2 and (1-√5)/2 as \varphi and \psi
+    pub named_constants: bool,
+    /// the variable of a polynomial written on its own
+    pub variable: String,
+}
+
+impl Default for LatexOptions {
+    fn default() -> LatexOptions {
+        LatexOptions {
+            precision: 3,
+            merge_signs: true,
+            closed_form: true,
+            named_constants: true,
+            variable: "x".to_string(),
+        }
+    }
+}
+
+// joins factors by juxtaposition, dropping a leading 1 and separating adjacent digits with \cdot
+pub(crate) fn join_factors(factors: &[String]) -> String {
+    let factors: Vec<&String> = factors.iter().filter(|f| !f.is_empty()).collect();
+    let mut res = String::new();
+    for (i, factor) in factors.iter().enumerate() {
+        if i == 0 && factor.as_str() == "1" && factors.len() > 1 {
+            continue;
+        }
+        let digits_meet = res.ends_with(|c: char| c.is_ascii_digit())
+            && factor.starts_with(|c: char| c.is_ascii_digit());
+        if digits_meet {
+            res.push_str(" \\cdot ");
+        }
+        res.push_str(factor);
+    }
+    if res.is_empty() {
+        res.push('1');
+    }
+    res
+}
+
+pub(crate) fn power(base: &str, exponent: &str) -> String {
+    format!("{}^{{{}}}", base, exponent)
+}
+
+impl Polynomial {
+    /// returns the polynomial in latex, for example x^{2} - x - 1
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        let notation = Notation::Latex(options);
+        notation.join_terms(&notation.polynomial_terms(
+            self.coefficients(),
+            &options.variable,
+            true,
+        ))
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns the relation and its base cases in latex,
+    /// for example f_n = f_{n-1} + f_{n-2}, \quad f_{0} = 0, \quad f_{1} = 1
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        let mut terms = Vec::new();
+        for (i, coefficient) in self.recurrence_coefficients().iter().enumerate() {
+            let magnitude = number(coefficient.abs(), options.precision);
+            if magnitude != "0" {
+                let term = format!("f_{{n-{}}}", i + 1);
+                terms.push((*coefficient < 0.0, join_factors(&[magnitude, term])));
+            }
+        }
+        let mut res = format!("f_n = {}", Notation::Latex(options).join_terms(&terms));
+        for (i, base_case) in self.base_cases().iter().enumerate() {
+            res.push_str(&format!(
+                ", \\quad f_{{{}}} = {}",
+                i,
+                number(*base_case, options.precision)
+            ));
+        }
+        res
+    }
+}
+
+impl RecurrenceSolution {
+    /// returns the solution in latex, with radicals if the options ask for a closed form and one
+    /// is identified, otherwise with numeric roots and coefficients
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        if options.closed_form {
+            if let Some(exact) = self.identify() {
+                return exact.to_latex(options);
+            }
+        }
+
+        let notation = Notation::Latex(options);
+        let mut terms = Vec::new();
+        for (polynomial, root) in self.terms() {
+            let root_power = match number(*root, options.precision).as_str() {
+                "1" => String::new(),
+                r if *root < 0.0 => power(&format!("\\left({}\\right)", r), "n"),
+                r => power(r, "n"),
+            };
+            let mut polynomial_terms =
+                notation.polynomial_terms(polynomial.coefficients(), "n", root.abs() < 1.0);
+            match polynomial_terms.len() {
+                0 => {}
+                // the terms of a root of one are written without parentheses, as in format
+                _ if root_power.is_empty() => terms.extend(polynomial_terms),
+                1 => {
+                    let (negative, magnitude) = polynomial_terms.remove(0);
+                    terms.push((negative, join_factors(&[magnitude, root_power])));
+                }
+                _ => {
+                    let polynomial =
+                        notation.parenthesized(&notation.join_terms(&polynomial_terms));
+                    terms.push((false, join_factors(&[polynomial, root_power])));
+                }
+            }
+        }
+        notation.join_terms(&terms)
+    }
+}
+
+impl ExactSolution {
+    /// returns the solution in latex, for example
+    /// \left(\frac{1}{\sqrt{5}}\right)\varphi^{n} - \left(\frac{1}{\sqrt{5}}\right)\psi^{n}
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        let mut terms = Vec::new();
+        for (coefficients, root) in self.terms() {
+            for (i, coefficient) in coefficients.iter().enumerate() {
+                if coefficient.is_zero() {
+                    continue;
+                }
+                let negative = coefficient.is_negative_form();
+                let magnitude = if negative {
+                    -coefficient.clone()
+                } else {
+                    coefficient.clone()
+                };
+
+                let mut factors = Vec::new();
+                if !magnitude.is_one() {
+                    factors.push(atomic_latex(&magnitude, options));
+                }
+                match i {
+                    0 => {}
+                    1 => factors.push("n".to_string()),
+                    _ => factors.push(power("n", &i.to_string())),
+                }
+                if !root.is_one() {
+                    factors.push(power(&atomic_latex(root, options), "n"));
+                }
+                terms.push((negative, join_factors(&factors)));
+            }
+        }
+        Notation::Latex(options).join_terms(&terms)
+    }
+}
+
+// returns \varphi or \psi if the number is (1 ± √5)/2 and the options name them
+fn named_constant(x: &Algebraic, options: &LatexOptions) -> Option<&'static str> {
+    match x {
+        Algebraic::Surd {
+            rational,
+            coefficient,
+            radicand: 5,
+        } if options.named_constants && *rational == Rational::new(1, 2) => {
+            if *coefficient == Rational::new(1, 2) {
+                Some("\\varphi")
+            } else if *coefficient == Rational::new(-1, 2) {
+                Some("\\psi")
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+// formats the number, wrapped in parentheses unless it is atomic
+fn atomic_latex(x: &Algebraic, options: &LatexOptions) -> String {
+    if x.is_atomic() || named_constant(x, options).is_some() {
+        x.to_latex(options)
+    } else {
+        format!("\\left({}\\right)", x.to_latex(options))
+    }
+}
+
+fn latex_radical(radicand: i128) -> String {
+    match radicand {
+        -1 => "i".to_string(),
+        d if d < 0 => format!("i\\sqrt{{{}}}", -d),
+        d => format!("\\sqrt{{{}}}", d),
+    }
+}
+
+fn fraction(numerator: &str, denominator: i128) -> String {
+    if denominator == 1 {
+        numerator.to_string()
+    } else {
+        format!("\\frac{{{}}}{{{}}}", numerator, denominator)
+    }
+}
+
+impl Algebraic {
+    /// returns the number in latex, for example \frac{1 + \sqrt{5}}{2}
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        if let Some(name) = named_constant(self, options) {
+            return name.to_string();
+        }
+        match self {
+            Algebraic::Rational(r) => {
+                let sign = if r.is_negative() { "-" } else { "" };
+                format!(
+                    "{}{}",
+                    sign,
+                    fraction(&r.numer().abs().to_string(), *r.denom())
+                )
+            }
+            Algebraic::Surd {
+                rational,
+                coefficient,
+                radicand,
+            } => {
+                let radical = latex_radical(*radicand);
+                if rational.is_zero() {
+                    let sign = if coefficient.is_negative() { "-" } else { "" };
+                    let numerator = coefficient.numer().abs();
+                    let denominator = *coefficient.denom();
+                    if numerator == 1 && denominator == *radicand {
+                        // p/d √d is written as p/√d
+                        return format!("{}\\frac{{1}}{{{}}}", sign, radical);
+                    }
+                    let scaled = if numerator == 1 {
+                        radical
+                    } else {
+                        format!("{}{}", numerator, radical)
+                    };
+                    format!("{}{}", sign, fraction(&scaled, denominator))
+                } else {
+                    let denominator = rational.denom()
+                        / gcd(*rational.denom(), *coefficient.denom())
+                        * coefficient.denom();
+                    let a = (rational * Rational::from(denominator)).to_integer();
+                    let b = (coefficient * Rational::from(denominator)).to_integer();
+                    let sign = if b < 0 { "-" } else { "+" };
+                    let scaled = if b.abs() == 1 {
+                        radical
+                    } else {
+                        format!("{}{}", b.abs(), radical)
+                    };
+                    fraction(&format!("{} {} {}", a, sign, scaled), denominator)
+                }
+            }
+            Algebraic::Sum(terms) => {
+                let terms: Vec<(bool, String)> = terms
+                    .iter()
+                    .map(|term| {
+                        if term.is_negative_form() {
+                            (true, (-term.clone()).to_latex(options))
+                        } else {
+                            (false, term.to_latex(options))
+                        }
+                    })
+                    .collect();
+                Notation::Latex(options).join_terms(&terms)
+            }
+            Algebraic::Product(factors) => {
+                let factors: Vec<String> =
+                    factors.iter().map(|x| atomic_latex(x, options)).collect();
+                factors.join(" \\cdot ")
+            }
+            Algebraic::Quotient(numerator, denominator) => format!(
+                "\\frac{{{}}}{{{}}}",
+                numerator.to_latex(options),
+                denominator.to_latex(options)
+            ),
+            Algebraic::Power(base, k) => power(&atomic_latex(base, options), &k.to_string()),
+            Algebraic::Root(radicand, k) => match k {
+                2 => format!("\\sqrt{{{}}}", radicand.to_latex(options)),
+                _ => format!("\\sqrt[{}]{{{}}}", k, radicand.to_latex(options)),
+            },
+            Algebraic::Neg(x) => format!("-{}", atomic_latex(x, options)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relation_and_polynomial() {
+        let options = LatexOptions::default();
+        let relation = RecurrenceRelation::new(vec![0.0, 1.0, 2.5], vec![1.0, 0.0, -0.5]);
+        assert_eq!(
+            relation.to_latex(&options),
+            "f_n = f_{n-1} - 0.5f_{n-3}, \\quad f_{0} = 0, \\quad f_{1} = 1, \\quad f_{2} = 2.5"
+        );
+        assert_eq!(
+            relation.characteristic_polynomial().to_latex(&options),
+            "x^{3} - x^{2} + 0.5"
+        );
+
+        let options = LatexOptions {
+            precision: 1,
+            merge_signs: false,
+            variable: "t".to_string(),
+            ..LatexOptions::default()
+        };
+        let polynomial = Polynomial::new(vec![-1.0, 0.04, -2.25, 1.0]);
+        assert_eq!(
+            polynomial.to_latex(&options),
+            "t^{3} + \\left(-2.2t^{2}\\right) + \\left(-1\\right)"
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let options = LatexOptions::default();
+        assert_eq!(
+            fibonacci.solve_exact().unwrap().to_latex(&options),
+            "\\left(\\frac{1}{\\sqrt{5}}\\right)\\varphi^{n} - \\left(\\frac{1}{\\sqrt{5}}\\right)\\psi^{n}"
+        );
+
+        let options = LatexOptions {
+            named_constants: false,
+            ..LatexOptions::default()
+        };
+        assert_eq!(
+            fibonacci.solve_exact().unwrap().to_latex(&options),
+            "\\left(\\frac{1}{\\sqrt{5}}\\right)\\left(\\frac{1 + \\sqrt{5}}{2}\\right)^{n} \
+             - \\left(\\frac{1}{\\sqrt{5}}\\right)\\left(\\frac{1 - \\sqrt{5}}{2}\\right)^{n}"
+        );
+
+        let options = LatexOptions {
+            closed_form: false,
+            ..LatexOptions::default()
+        };
+        assert_eq!(
+            fibonacci.solve().to_latex(&options),
+            "-0.447\\left(-0.618\\right)^{n} + 0.447 \\cdot 1.618^{n}"
+        );
+        // f(n) = 2f(n-1) - f(n-2) with f(0) = 1 and f(1) = 3 is 2n + 1
+        let relation = RecurrenceRelation::new(vec![1.0, 3.0], vec![2.0, -1.0]);
+        assert_eq!(relation.solve().to_latex(&options), "2n + 1");
+    }
+}
@@ -1,9 +1,61 @@
+mod algebraic;
+mod asymptotics;
+mod autoregressive;
+mod berlekamp_massey;
+mod dynamics;
+mod equivalence;
+mod exact_solution;
+mod explain;
+mod expression;
+mod filter;
+mod forecast;
+mod format;
+mod identify;
+mod latex;
+mod multistep;
+mod nonlinear;
+mod plot;
 mod polynomial;
+mod random;
+mod rational_polynomial;
 mod recurrence_relation;
 mod recurrence_relation_parser;
 mod recurrence_solution;
+mod sequence_operations;
+mod sequence_transforms;
+#[cfg(feature = "serde")]
+mod serialization;
+mod stability;
+mod state_space;
+mod stochastic;
+mod transfer_function;
 mod utilities;
+mod z_transform;
 
+pub use algebraic::Algebraic;
+pub use asymptotics::{Asymptotics, Growth};
+pub use autoregressive::{ArMethod, ArModel, OrderCriterion};
+pub use dynamics::{Cycle, IteratedMap, LYAPUNOV_TOLERANCE};
+pub use equivalence::Equivalence;
+pub use exact_solution::ExactSolution;
+pub use explain::{Explanation, ExplanationFormat};
+pub use expression::{EvaluationError, ParseExpressionError};
+pub use filter::Filter;
+pub use forecast::Forecast;
+pub use format::FormatOptions;
+pub use identify::{identify_constant, integer_relation};
+pub use latex::LatexOptions;
+pub use multistep::MultistepMethod;
+pub use nonlinear::NonlinearRecurrence;
+pub use plot::ascii_plot;
 pub use polynomial::Polynomial;
+pub use random::{Noise, Rng};
+pub use rational_polynomial::{Rational, RationalPolynomial};
 pub use recurrence_relation::RecurrenceRelation;
+pub use recurrence_relation_parser::ParseRecurrenceError;
 pub use recurrence_solution::RecurrenceSolution;
+pub use stability::Stability;
+pub use state_space::StateSpace;
+pub use stochastic::{MonteCarlo, StochasticRecurrence};
+pub use transfer_function::TransferFunction;
+pub use z_transform::ZTransform;
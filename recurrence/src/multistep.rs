@@ -0,0 +1,228 @@
+use crate::polynomial::Polynomial;
+use crate::stability::{root_stability, Stability};
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+// relative tolerance for the consistency conditions and for points where σ vanishes
+const TOLERANCE: f64 = 1e-9;
+
+/// a linear multistep method α_k y(n+k) + ... + α_0 y(n) = h(β_k f(n+k) + ... + β_0 f(n)),
+/// given by its characteristic polynomials ρ(x) = Σ α_j x^j and σ(x) = Σ β_j x^j
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultistepMethod {
+    rho: Polynomial,
+    sigma: Polynomial,
+}
+
+// evaluates the polynomial with the given coefficients at a complex point
+fn evaluate(coefficients: &[f64], z: Complex<f64>) -> Complex<f64> {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Complex::new(0.0, 0.0), |res, c| res * z + c)
+}
+
+impl MultistepMethod {
+    /// creates a method from ρ and σ, the method is explicit when σ has lower degree than ρ
+    pub fn new(rho: Polynomial, sigma: Polynomial) -> MultistepMethod {
+        assert!(
+            sigma.degree() <= rho.degree(),
+            "σ cant have a higher degree than ρ"
+        );
+        MultistepMethod { rho, sigma }
+    }
+
+    pub fn rho(&self) -> &Polynomial {
+        &self.rho
+    }
+
+    pub fn sigma(&self) -> &Polynomial {
+        &self.sigma
+    }
+
+    /// returns the number of steps k
+    pub fn steps(&self) -> usize {
+        self.rho.degree()
+    }
+
+    /// returns whether y(n+k) is given explicitly, which is when β_k is zero
+    pub fn is_explicit(&self) -> bool {
+        self.sigma
+            .coefficients()
+            .get(self.steps())
+            .map_or(true, |beta| *beta == 0.0)
+    }
+
+    /// returns where the roots of ρ lie relative to the unit circle
+    pub fn zero_stability(&self) -> Stability {
+        root_stability(&self.rho)
+    }
+
+    /// returns whether the method satisfies the root condition, that is every root of ρ lies
+    /// inside or on the unit circle and those on it are simple
+    pub fn is_zero_stable(&self) -> bool {
+        self.zero_stability() != Stability::Unstable
+    }
+
+    // returns C_q = Σ j^q/q! α_j - Σ j^(q-1)/(q-1)! β_j along with the size of the terms summed,
+    // the method has order p when C_0 = ... = C_p = 0
+    fn error_coefficient(&self, q: usize) -> (f64, f64) {
+        let mut sum = 0.0;
+        let mut scale = 0.0;
+        for (j, alpha) in self.rho.coefficients().iter().enumerate() {
+            let term = (j as f64).powi(q as i32) / factorial(q) * alpha;
+            sum += term;
+            scale += term.abs();
+        }
+        if q > 0 {
+            for (j, beta) in self.sigma.coefficients().iter().enumerate() {
+                let term = (j as f64).powi(q as i32 - 1) / factorial(q - 1) * beta;
+                sum -= term;
+                scale += term.abs();
+            }
+        }
+        (sum, scale)
+    }
+
+    fn vanishes(&self, q: usize) -> bool {
+        let (sum, scale) = self.error_coefficient(q);
+        sum.abs() <= TOLERANCE * scale
+    }
+
+    /// returns the order of consistency p, the largest p with C_0 = ... = C_p = 0
+    /// returns None if the method is not consistent, that is if ρ(1) or ρ'(1) - σ(1) is not zero
+    pub fn order(&self) -> Option<usize> {
+        if !self.vanishes(0) || !self.vanishes(1) {
+            return None;
+        }
+        // a k step method has order at most 2k, so the search stops past that
+        let mut order = 1;
+        while order <= 2 * self.steps() && self.vanishes(order + 1) {
+            order += 1;
+        }
+        Some(order)
+    }
+
+    /// returns whether the method is consistent, that is has order at least one
+    pub fn is_consistent(&self) -> bool {
+        self.order().is_some()
+    }
+
+    /// returns whether the method converges, which by the dahlquist equivalence theorem is when
+    /// it is consistent and zero-stable
+    pub fn is_convergent(&self) -> bool {
+        self.is_consistent() && self.is_zero_stable()
+    }
+
+    /// returns the error constant C_(p+1)/σ(1), or None if the method is not consistent
+    pub fn error_constant(&self) -> Option<f64> {
+        let order = self.order()?;
+        let sigma_one: f64 = self.sigma.coefficients().iter().sum();
+        Some(self.error_coefficient(order + 1).0 / sigma_one)
+    }
+
+    /// returns the boundary locus hλ = ρ(e^iθ)/σ(e^iθ) at the given number of equally spaced θ in
+    /// [0, 2π], as (θ, hλ) pairs
+    /// the boundary of the region of absolute stability lies on this curve
+    /// points where σ(e^iθ) is zero, and so hλ is infinite, are left out
+    pub fn boundary_locus(&self, points: usize) -> Vec<(f64, Complex<f64>)> {
+        let scale: f64 = self.sigma.coefficients().iter().map(|b| b.abs()).sum();
+        let mut locus = Vec::new();
+        for i in 0..points {
+            let theta = if points > 1 {
+                2.0 * PI * i as f64 / (points - 1) as f64
+            } else {
+                0.0
+            };
+            let z = Complex::from_polar(1.0, theta);
+            let sigma = evaluate(self.sigma.coefficients(), z);
+            if sigma.norm() <= TOLERANCE * scale {
+                continue;
+            }
+            locus.push((theta, evaluate(self.rho.coefficients(), z) / sigma));
+        }
+        locus
+    }
+
+    /// returns the boundary locus as csv with the columns theta, re and im
+    pub fn boundary_locus_csv(&self, points: usize) -> String {
+        let mut csv = "theta,re,im\n".to_string();
+        for (theta, z) in self.boundary_locus(points) {
+            csv.push_str(&format!("{},{},{}\n", theta, z.re, z.im));
+        }
+        csv
+    }
+}
+
+fn factorial(n: usize) -> f64 {
+    (1..=n).map(|i| i as f64).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(rho: Vec<f64>, sigma: Vec<f64>) -> MultistepMethod {
+        MultistepMethod::new(Polynomial::new(rho), Polynomial::new(sigma))
+    }
+
+    #[test]
+    fn test_order() {
+        // euler's method, y(n+1) - y(n) = hf(n)
+        let euler = method(vec![-1.0, 1.0], vec![1.0]);
+        assert!(euler.is_explicit());
+        assert_eq!(euler.order(), Some(1));
+        assert_eq!(euler.error_constant(), Some(0.5));
+        assert_eq!(euler.zero_stability(), Stability::MarginallyStable);
+        assert!(euler.is_convergent());
+
+        // two step adams-bashforth
+        let adams_bashforth = method(vec![0.0, -1.0, 1.0], vec![-0.5, 1.5]);
+        assert_eq!(adams_bashforth.order(), Some(2));
+        assert!((adams_bashforth.error_constant().unwrap() - 5.0 / 12.0).abs() < 1e-12);
+
+        // two step bdf
+        let bdf = method(vec![0.5, -2.0, 1.5], vec![0.0, 0.0, 1.0]);
+        assert!(!bdf.is_explicit());
+        assert_eq!(bdf.order(), Some(2));
+        assert!(bdf.is_convergent());
+
+        // simpson's rule, which reaches the highest order k + 2 of a zero-stable k step method
+        let simpson = method(vec![-1.0, 0.0, 1.0], vec![1.0 / 3.0, 4.0 / 3.0, 1.0 / 3.0]);
+        assert_eq!(simpson.order(), Some(4));
+        assert!(simpson.is_zero_stable());
+
+        let inconsistent = method(vec![-1.0, 1.0], vec![2.0]);
+        assert_eq!(inconsistent.order(), None);
+        assert!(!inconsistent.is_convergent());
+    }
+
+    #[test]
+    fn test_zero_stability() {
+        // the explicit two step method of order three, with ρ having the root -5
+        let method = method(vec![-5.0, 4.0, 1.0], vec![2.0, 4.0]);
+        assert_eq!(method.order(), Some(3));
+        assert_eq!(method.zero_stability(), Stability::Unstable);
+        assert!(!method.is_convergent());
+    }
+
+    #[test]
+    fn test_boundary_locus() {
+        // for euler's method the boundary is the circle of radius one about -1
+        let euler = method(vec![-1.0, 1.0], vec![1.0]);
+        let locus = euler.boundary_locus(9);
+        assert_eq!(locus.len(), 9);
+        assert!(locus
+            .iter()
+            .all(|(_, z)| ((z + 1.0).norm() - 1.0).abs() < 1e-12));
+        assert!((locus[4].1 - Complex::new(-2.0, 0.0)).norm() < 1e-12);
+
+        // the trapezoidal rule has σ(-1) = 0, so θ = π is left out
+        let trapezoidal = method(vec![-1.0, 1.0], vec![0.5, 0.5]);
+        let csv = trapezoidal.boundary_locus_csv(3);
+        assert_eq!(csv.lines().count(), 3);
+        assert_eq!(csv.lines().next(), Some("theta,re,im"));
+        assert!(csv.lines().nth(1).unwrap().starts_with("0,0,0"));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::expression::{parse_expression, EvaluationError, Expression, ParseExpressionError};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+// the default largest number of terms computed for one sequence
+const STEP_LIMIT: usize = 1_000_000;
+
+/// a recurrence f(n) = g(n, f(0), ..., f(n-1)) given by an arbitrary expression, for example
+/// x(n) = r*x(n-1)*(1-x(n-1)), x(0) = 0.5, r = 3.7
+/// expressions may use numbers, n, the constants pi and e, named parameters, earlier terms at
+/// any index such as f(n-2) or f(floor(n/2)), the operators + - * / % ^ and mod, and the functions
+/// sin, cos, tan, exp, ln, sqrt, abs, floor and ceil
+/// mod has the lowest precedence, so f(n-1) + f(n-2) mod 7 takes the whole sum mod 7
+/// terms are remembered once computed, so each is only computed once
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonlinearRecurrence {
+    sequence: String,
+    expression: Expression,
+    source: String,
+    base_cases: Vec<f64>,
+    parameters: HashMap<String, f64>,
+    step_limit: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    terms: Vec<f64>,
+}
+
+impl NonlinearRecurrence {
+    /// returns the name of the sequence, such as f
+    pub fn sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    /// returns the base cases f(0), f(1), ...
+    pub fn base_cases(&self) -> &[f64] {
+        &self.base_cases
+    }
+
+    // returns the right side of the recurrence
+    pub(crate) fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// returns the values of the named parameters
+    pub fn parameters(&self) -> &HashMap<String, f64> {
+        &self.parameters
+    }
+
+    /// sets the value of a parameter, forgetting the terms computed with the old value
+    pub fn set_parameter(&mut self, name: &str, value: f64) {
+        self.parameters.insert(name.to_string(), value);
+        self.terms.clear();
+    }
+
+    /// returns the largest number of terms which will be computed
+    pub fn step_limit(&self) -> usize {
+        self.step_limit
+    }
+
+    /// sets the largest number of terms which will be computed
+    pub fn set_step_limit(&mut self, step_limit: usize) {
+        self.step_limit = step_limit;
+    }
+
+    /// returns the term f(n), computing and remembering every term before it
+    /// earlier terms are taken at their index rounded down, and must be before n
+    pub fn term(&mut self, n: usize) -> Result<f64, EvaluationError> {
+        if n >= self.step_limit {
+            return Err(EvaluationError::StepLimitExceeded);
+        }
+        while self.terms.len() <= n {
+            let i = self.terms.len();
+            let next = if i < self.base_cases.len() {
+                self.base_cases[i]
+            } else {
+                let terms = &self.terms;
+                let mut term = |index: f64| {
+                    let index = index.floor();
+                    if index >= 0.0 && index < i as f64 {
+                        Ok(terms[index as usize])
+                    } else {
+                        Err(EvaluationError::IndexOutOfRange { n: i, index })
+                    }
+                };
+                self.expression.evaluate(i, &self.parameters, &mut term)?
+            };
+            self.terms.push(next);
+        }
+        Ok(self.terms[n])
+    }
+
+    /// returns the first n terms
+    pub fn terms(&mut self, n: usize) -> Result<Vec<f64>, EvaluationError> {
+        if n > 0 {
+            self.term(n - 1)?;
+        }
+        Ok(self.terms[..n].to_vec())
+    }
+}
+
+// writes the recurrence as it was given, with its base cases and parameters
+impl fmt::Display for NonlinearRecurrence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(n) = {}", self.sequence, self.source)?;
+        for (i, base_case) in self.base_cases.iter().enumerate() {
+            write!(f, ", {}({}) = {}", self.sequence, i, base_case)?;
+        }
+        let mut parameters: Vec<_> = self.parameters.iter().collect();
+        parameters.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in parameters {
+            write!(f, ", {} = {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+// splits on the commas which are not inside parentheses
+fn split_equations(s: &str) -> Vec<&str> {
+    let mut equations = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                equations.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    equations.push(s[start..].trim());
+    equations
+}
+
+impl FromStr for NonlinearRecurrence {
+    type Err = ParseExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut recurrence = None;
+        let mut base_cases: Vec<(String, usize, f64)> = Vec::new();
+        let mut parameters = HashMap::new();
+
+        for equation in split_equations(s) {
+            let (left, right) = equation
+                .split_once('=')
+                .ok_or(ParseExpressionError::MissingEquals)?;
+            let (left, right) = (left.trim(), right.trim());
+
+            // name(n) = expression, name(k) = value, or name = value
+            match left.split_once('(') {
+                Some((name, argument)) => {
+                    let argument = argument
+                        .strip_suffix(')')
+                        .ok_or_else(|| ParseExpressionError::UnexpectedToken(left.to_string()))?
+                        .trim();
+                    let name = name.trim().to_string();
+                    if argument == "n" {
+                        if recurrence.is_some() {
+                            return Err(ParseExpressionError::MultipleRecurrence);
+                        }
+                        let expression = parse_expression(right, &name)?;
+                        recurrence = Some((name, expression, right.to_string()));
+                    } else {
+                        let index = argument
+                            .parse()
+                            .map_err(|_| ParseExpressionError::UnexpectedToken(left.to_string()))?;
+                        let value = right
+                            .parse()
+                            .map_err(|_| ParseExpressionError::ParseFloatError)?;
+                        base_cases.push((name, index, value));
+                    }
+                }
+                None => {
+                    let value = right
+                        .parse()
+                        .map_err(|_| ParseExpressionError::ParseFloatError)?;
+                    if parameters.insert(left.to_string(), value).is_some() {
+                        return Err(ParseExpressionError::MultipleParameter);
+                    }
+                }
+            }
+        }
+
+        let (sequence, expression, source) =
+            recurrence.ok_or(ParseExpressionError::NoRecurrence)?;
+        // a base case of another sequence, such as y(0) for x(n), is not part of the recurrence
+        if let Some((name, index, _)) = base_cases.iter().find(|(name, _, _)| *name != sequence) {
+            return Err(ParseExpressionError::UnexpectedToken(format!(
+                "{}({})",
+                name, index
+            )));
+        }
+        base_cases.sort_by_key(|(_, index, _)| *index);
+        for (i, (_, index, _)) in base_cases.iter().enumerate() {
+            if *index < i {
+                return Err(ParseExpressionError::MultipleBaseCase);
+            }
+            if *index > i {
+                return Err(ParseExpressionError::BaseCaseGap);
+            }
+        }
+
+        Ok(NonlinearRecurrence {
+            sequence,
+            expression,
+            source,
+            base_cases: base_cases.into_iter().map(|(_, _, value)| value).collect(),
+            parameters,
+            step_limit: STEP_LIMIT,
+            terms: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms() {
+        let mut logistic: NonlinearRecurrence = "x(n) = r*x(n-1)*(1-x(n-1)), x(0) = 0.5, r = 2"
+            .parse()
+            .unwrap();
+        assert_eq!(logistic.terms(3).unwrap(), vec![0.5, 0.5, 0.5]);
+        logistic.set_parameter("r", 4.0);
+        assert_eq!(logistic.terms(3).unwrap(), vec![0.5, 1.0, 0.0]);
+        assert_eq!(
+            logistic.to_string(),
+            "x(n) = r*x(n-1)*(1-x(n-1)), x(0) = 0.5, r = 4"
+        );
+
+        let mut squares: NonlinearRecurrence = "f(n) = f(n-1)^2 + 1, f(0) = 0".parse().unwrap();
+        assert_eq!(squares.terms(5).unwrap(), vec![0.0, 1.0, 2.0, 5.0, 26.0]);
+
+        let mut fibonacci: NonlinearRecurrence = "f(n) = f(n-1) + f(n-2) mod 7, f(0) = 0, f(1) = 1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            fibonacci.terms(10).unwrap(),
+            vec![0.0, 1.0, 1.0, 2.0, 3.0, 5.0, 1.0, 6.0, 0.0, 6.0]
+        );
+
+        // T(n) = 2T(n/2) + n, which remembers the terms it has seen
+        let mut divide: NonlinearRecurrence =
+            "T(n) = 2*T(n/2) + n, T(0) = 0, T(1) = 0".parse().unwrap();
+        assert_eq!(divide.term(1024).unwrap(), 10240.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut recurrence: NonlinearRecurrence = "f(n) = f(n-2) + 1, f(0) = 0".parse().unwrap();
+        assert_eq!(
+            recurrence.term(1),
+            Err(EvaluationError::IndexOutOfRange { n: 1, index: -1.0 })
+        );
+
+        let mut recurrence: NonlinearRecurrence = "f(n) = f(n-1) + 1, f(0) = 0".parse().unwrap();
+        recurrence.set_step_limit(100);
+        assert_eq!(recurrence.term(99), Ok(99.0));
+        assert_eq!(
+            recurrence.term(100),
+            Err(EvaluationError::StepLimitExceeded)
+        );
+
+        let mut recurrence: NonlinearRecurrence = "f(n) = a*f(n-1), f(0) = 1".parse().unwrap();
+        assert_eq!(
+            recurrence.term(1),
+            Err(EvaluationError::UnknownParameter("a".to_string()))
+        );
+
+        assert_eq!(
+            "f(n) = f(n-1), f(1) = 1".parse::<NonlinearRecurrence>(),
+            Err(ParseExpressionError::BaseCaseGap)
+        );
+        assert_eq!(
+            "f(0) = 1".parse::<NonlinearRecurrence>(),
+            Err(ParseExpressionError::NoRecurrence)
+        );
+        assert_eq!(
+            "x(n) = x(n-1) + 1, y(0) = 5".parse::<NonlinearRecurrence>(),
+            Err(ParseExpressionError::UnexpectedToken("y(0)".to_string()))
+        );
+        assert_eq!(
+            "x(n) = r*x(n-1), x(0) = 1, r = 3, r = 4".parse::<NonlinearRecurrence>(),
+            Err(ParseExpressionError::MultipleParameter)
+        );
+    }
+}
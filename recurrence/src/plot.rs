@@ -0,0 +1,78 @@
+use crate::format::number;
+
+// writes large values in scientific notation so that the labels stay short
+fn label(x: f64) -> String {
+    if x.abs() >= 1e6 {
+        format!("{:.3e}", x)
+    } else {
+        number(x, 3)
+    }
+}
+
+/// returns a plot of the values as text, one column per value and the given number of rows,
+/// with the largest and smallest values marked on the left
+/// values which are not finite are left out
+pub fn ascii_plot(values: &[f64], height: usize) -> String {
+    let finite = values.iter().copied().filter(|x| x.is_finite());
+    let max = finite.clone().fold(f64::NEG_INFINITY, f64::max);
+    let min = finite.fold(f64::INFINITY, f64::min);
+    if values.is_empty() || height == 0 || max < min {
+        return String::new();
+    }
+
+    // the row of each value, counting down from the top
+    let rows = height.max(2);
+    let range = if max > min { max - min } else { 1.0 };
+    let row = |x: f64| ((max - x) / range * (rows - 1) as f64).round() as usize;
+
+    let labels = [label(max), label(min)];
+    let width = labels.iter().map(|label| label.len()).max().unwrap();
+    let mut plot = String::new();
+    for r in 0..rows {
+        let label = match r {
+            0 => &labels[0],
+            _ if r == rows - 1 => &labels[1],
+            _ => "",
+        };
+        let line: String = values
+            .iter()
+            .map(|x| {
+                if x.is_finite() && row(*x) == r {
+                    '*'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        plot.push_str(&format!(
+            "{:>width$} |{}\n",
+            label,
+            line.trim_end(),
+            width = width
+        ));
+    }
+    plot.push_str(&format!(
+        "{:>width$} +{}\n",
+        "",
+        "-".repeat(values.len()),
+        width = width
+    ));
+    plot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_plot() {
+        let plot = ascii_plot(&[0.0, 1.0, 2.0, 1.0, 0.0], 3);
+        assert_eq!(plot, "2 |  *\n  | * *\n0 |*   *\n  +-----\n");
+        assert_eq!(ascii_plot(&[], 3), "");
+        assert_eq!(ascii_plot(&[1.5, 1.5], 2), "1.5 |**\n1.5 |\n    +--\n");
+        assert_eq!(
+            ascii_plot(&[0.0, 2e300, f64::INFINITY], 2),
+            "2.000e300 | *\n        0 |*\n          +---\n"
+        );
+    }
+}
@@ -1,7 +1,17 @@
+use crate::format::FormatOptions;
+use crate::rational_polynomial::{gcd, rational_to_f64, RationalPolynomial};
 use crate::utilities::*;
 use nalgebra::DMatrix;
+use num_complex::Complex;
 use std::fmt;
 
+// the eigenvalues of a root of multiplicity m spread out by about ε^(1/m) relative to its size, so
+// eigenvalues this close relative to their size may be one repeated root
+const REPEATED_ROOT_DISTANCE: f64 = 1e-4;
+
+// the derivative at a repeated root, relative to the size of its terms, is at most this
+const REPEATED_ROOT_RESIDUAL: f64 = 1e-6;
+
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct Polynomial {
     coefficients: Vec<f64>,
@@ -20,6 +30,10 @@ impl Polynomial {
         self.coefficients.len() - 1
     }
 
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
     // returns the companion matrix of the polynomial
     pub fn companion_matrix(&self) -> DMatrix<f64> {
         let mut elements = Vec::new();
@@ -31,13 +45,116 @@ impl Polynomial {
                     elements.push(0.0);
                 }
             }
-            elements.push(-1.0 * (self.coefficients[i] / self.coefficients[self.degree()]));
+            elements.push(-(self.coefficients[i] / self.coefficients[self.degree()]));
         }
         DMatrix::from_row_slice(self.degree(), self.degree(), &elements)
     }
 
+    // returns the factors of the polynomial over the rationals with their multiplicities, which are
+    // irreducible unless the search for a factor exceeds its budget, see RationalPolynomial::factor
+    // returns None if the coefficients are not all rational, the exact arithmetic overflows or a
+    // coefficient is too large to search
+    pub fn factor(&self) -> Option<Vec<(RationalPolynomial, usize)>> {
+        RationalPolynomial::from_polynomial(self).and_then(|polynomial| polynomial.factor())
+    }
+
     // returns a vector of the roots of a polynomial
+    // rational polynomials are factored first so that rational roots and multiplicities are exact
+    // and only the irrational factors are solved numerically
     pub fn roots(&self) -> Vec<(f64, usize)> {
+        let factors = match self.factor() {
+            Some(factors) => factors,
+            None => return self.numeric_roots(),
+        };
+
+        let mut roots = Vec::new();
+        for (factor, multiplicity) in factors {
+            if factor.degree() == 1 {
+                let coefficients = factor.coefficients();
+                roots.push((
+                    rational_to_f64(&(-coefficients[0] / coefficients[1])),
+                    multiplicity,
+                ));
+            } else {
+                for (root, count) in factor.to_polynomial().numeric_roots() {
+                    roots.push((root, count * multiplicity));
+                }
+            }
+        }
+        roots
+    }
+
+    // returns the complex roots of the polynomial with their multiplicities
+    // as with roots, rational polynomials are factored first and only the irrational factors are
+    // solved numerically, except for cyclotomic factors whose roots are the roots of unity
+    pub fn complex_roots(&self) -> Vec<(Complex<f64>, usize)> {
+        let factors = match self.factor() {
+            Some(factors) => factors,
+            None => return self.numeric_complex_roots(),
+        };
+
+        let mut roots = Vec::new();
+        for (factor, multiplicity) in factors {
+            if factor.degree() == 1 {
+                let coefficients = factor.coefficients();
+                let root = rational_to_f64(&(-coefficients[0] / coefficients[1]));
+                roots.push((Complex::new(root, 0.0), multiplicity));
+            } else if let Some(k) = factor.cyclotomic_index() {
+                // the primitive k-th roots of unity, which lie exactly on the unit circle
+                for j in (1..k).filter(|j| gcd(*j as i128, k as i128) == 1) {
+                    let angle = 2.0 * std::f64::consts::PI * j as f64 / k as f64;
+                    roots.push((Complex::from_polar(1.0, angle), multiplicity));
+                }
+            } else {
+                for (root, count) in factor.to_polynomial().numeric_complex_roots() {
+                    roots.push((root, count * multiplicity));
+                }
+            }
+        }
+        roots
+    }
+
+    // returns whether the derivative vanishes at z relative to the size of its terms, which
+    // confirms that eigenvalues close to z are one repeated root rather than distinct roots
+    fn is_repeated_root(&self, z: Complex<f64>) -> bool {
+        let mut derivative = Complex::new(0.0, 0.0);
+        let mut scale = 0.0;
+        for (k, c) in self.coefficients.iter().enumerate().skip(1).rev() {
+            derivative = derivative * z + c * k as f64;
+        }
+        for (k, c) in self.coefficients.iter().enumerate().skip(1) {
+            scale += (c * k as f64).abs() * z.norm().powi(k as i32 - 1);
+        }
+        derivative.norm() <= REPEATED_ROOT_RESIDUAL * scale
+    }
+
+    // returns the complex eigenvalues of the companion matrix, grouping those which are close
+    // relative to their size and where the derivative vanishes
+    // a repeated root is the mean of its eigenvalues, which splits the error of each between them
+    fn numeric_complex_roots(&self) -> Vec<(Complex<f64>, usize)> {
+        let mut groups: Vec<(Complex<f64>, usize)> = Vec::new();
+        for new_eigenvalue in self.companion_matrix().complex_eigenvalues().iter() {
+            match groups.iter_mut().find(|(sum, count)| {
+                let eigenvalue = *sum / *count as f64;
+                let distance = (new_eigenvalue - eigenvalue).norm();
+                distance <= REPEATED_ROOT_DISTANCE * eigenvalue.norm().max(1.0)
+                    && self.is_repeated_root(eigenvalue)
+            }) {
+                Some((sum, count)) => {
+                    *sum += new_eigenvalue;
+                    *count += 1;
+                }
+                None => groups.push((*new_eigenvalue, 1)),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(sum, count)| (sum / count as f64, count))
+            .collect()
+    }
+
+    // returns the roots of the polynomial from the eigenvalues of its companion matrix
+    fn numeric_roots(&self) -> Vec<(f64, usize)> {
         let companion = self.companion_matrix();
         // look into increasing number of iterations
         let schur = companion
@@ -75,19 +192,14 @@ impl Polynomial {
     }
 }
 
+// writes the polynomial with the default format options and the precision of the format string
 impl fmt::Display for Polynomial {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut res = String::new();
-        for (pow, coefficient) in self.coefficients.iter().enumerate().rev() {
-            if pow == 0 {
-                res.push_str(&format!("{:.3}", coefficient));
-            } else if pow == 1 {
-                res.push_str(&format!("{:.3}n", coefficient));
-            } else {
-                res.push_str(&format!("{:.3}n^{}", coefficient, pow));
-            }
+        let mut options = FormatOptions::default();
+        if let Some(precision) = f.precision() {
+            options.precision = precision;
         }
-        write!(f, "{}", res)
+        write!(f, "{}", self.format(&options))
     }
 }
 
@@ -95,6 +207,57 @@ impl fmt::Display for Polynomial {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_complex_roots() {
+        // (x^2 + 1)(x - 2)^2
+        let polynomial = Polynomial::new(vec![4.0, -4.0, 5.0, -4.0, 1.0]);
+        let mut roots = polynomial.complex_roots();
+        roots.sort_by(|a, b| a.0.im.partial_cmp(&b.0.im).unwrap());
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots[1], (Complex::new(2.0, 0.0), 2));
+        assert!((roots[0].0 - Complex::new(0.0, -1.0)).norm() < 1e-12);
+        assert!((roots[2].0 - Complex::new(0.0, 1.0)).norm() < 1e-12);
+
+        let polynomial = Polynomial::new(vec![-std::f64::consts::PI, 0.0, 1.0]);
+        let roots = polynomial.complex_roots();
+        assert_eq!(roots.len(), 2);
+        assert!(roots
+            .iter()
+            .all(
+                |(root, count)| (root.norm() - std::f64::consts::PI.sqrt()).abs() < 1e-12
+                    && *count == 1
+            ));
+
+        // (x^2 + √2x + 1)^2 is not rational, its double roots are grouped from the eigenvalues
+        // while the simple roots of x^2 - 1.9999998x + 1, 9e-4 apart, are not
+        let sqrt_2 = std::f64::consts::SQRT_2;
+        let polynomial = Polynomial::new(vec![1.0, 2.0 * sqrt_2, 4.0, 2.0 * sqrt_2, 1.0]);
+        let roots = polynomial.complex_roots();
+        assert_eq!(roots.len(), 2);
+        assert!(roots
+            .iter()
+            .all(|(root, count)| { (root.norm() - 1.0).abs() < 1e-6 && *count == 2 }));
+        let polynomial = Polynomial::new(vec![1.0, -1.9999998, 1.0]);
+        assert_eq!(polynomial.complex_roots().len(), 2);
+
+        // (x^2 + x + 1)^2, whose roots are the primitive cube roots of unity
+        let polynomial = Polynomial::new(vec![1.0, 2.0, 3.0, 2.0, 1.0]);
+        let roots = polynomial.complex_roots();
+        assert_eq!(roots.len(), 2);
+        for (root, count) in roots {
+            assert_eq!(count, 2);
+            assert!((root.powi(3) - 1.0).norm() < 1e-12);
+        }
+
+        // the constant is too large for the rational root test, so the roots are numeric
+        let polynomial = Polynomial::new(vec![-1e15, 0.0, 1.0]);
+        let roots = polynomial.complex_roots();
+        assert_eq!(roots.len(), 2);
+        assert!(roots
+            .iter()
+            .all(|(root, _)| (root.norm() - 1e15f64.sqrt()).abs() < 1e-6));
+    }
+
     #[test]
     fn test_companion_matrix() {
         let polynomial1 = Polynomial::new(vec![-1.0, -1.0, 1.0]);
@@ -129,5 +292,19 @@ mod tests {
         let polynomial2 = Polynomial::new(vec![8.0, 12.0, 6.0, 1.0]);
         let expected_roots2 = vec![(-2.0, 3)];
         assert!(float_counts_within(polynomial2.roots(), expected_roots2));
+
+        // rational roots are found exactly
+        let polynomial3 = Polynomial::new(vec![-2.0, -1.0, 1.0]);
+        let mut roots3 = polynomial3.roots();
+        roots3.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(roots3, vec![(-1.0, 1), (2.0, 1)]);
+
+        let polynomial4 = Polynomial::new(vec![-2.0, -3.0, 4.0, 3.0, -4.0, 1.0]);
+        let expected_roots4 = vec![
+            (2.0, 1),
+            (1.618_033_988_749_895, 2),
+            (-0.618_033_988_749_894_9, 2),
+        ];
+        assert!(float_counts_within(polynomial4.roots(), expected_roots4));
     }
 }
@@ -0,0 +1,139 @@
+use std::f64::consts::PI;
+
+/// a seeded pseudorandom number generator, xoshiro256** seeded through splitmix64
+/// the same seed always gives the same numbers, on every platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// creates a generator from the given seed
+    pub fn new(seed: u64) -> Rng {
+        // splitmix64 spreads the seed over the state, which must not be all zero
+        let mut x = seed;
+        let mut state = [0; 4];
+        for s in state.iter_mut() {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *s = z ^ (z >> 31);
+        }
+        Rng { state }
+    }
+
+    /// returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// returns a number drawn uniformly from [0, 1)
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// returns a number drawn from the standard normal distribution, using the box-muller transform
+    pub fn normal(&mut self) -> f64 {
+        // 1 - u is in (0, 1], so its logarithm is finite
+        let u = 1.0 - self.uniform();
+        let v = self.uniform();
+        (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()
+    }
+}
+
+/// a distribution for the random terms of a stochastic recurrence
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Noise {
+    /// normal with the given mean and standard deviation
+    Normal { mean: f64, standard_deviation: f64 },
+    /// uniform on [low, high)
+    Uniform { low: f64, high: f64 },
+    /// 1 with probability p and 0 otherwise
+    Bernoulli { p: f64 },
+}
+
+impl Noise {
+    /// draws a value from the distribution
+    pub fn sample(&self, rng: &mut Rng) -> f64 {
+        match *self {
+            Noise::Normal {
+                mean,
+                standard_deviation,
+            } => mean + standard_deviation * rng.normal(),
+            Noise::Uniform { low, high } => low + (high - low) * rng.uniform(),
+            Noise::Bernoulli { p } => {
+                if rng.uniform() < p {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// returns the mean of the distribution
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Noise::Normal { mean, .. } => mean,
+            Noise::Uniform { low, high } => (low + high) / 2.0,
+            Noise::Bernoulli { p } => p,
+        }
+    }
+
+    /// returns the variance of the distribution
+    pub fn variance(&self) -> f64 {
+        match *self {
+            Noise::Normal {
+                standard_deviation, ..
+            } => standard_deviation * standard_deviation,
+            Noise::Uniform { low, high } => (high - low) * (high - low) / 12.0,
+            Noise::Bernoulli { p } => p * (1.0 - p),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let xs: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(xs, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(xs[0], Rng::new(43).next_u64());
+
+        // the sample moments of each distribution are close to the true ones
+        let distributions = [
+            Noise::Normal {
+                mean: 1.0,
+                standard_deviation: 2.0,
+            },
+            Noise::Uniform {
+                low: -1.0,
+                high: 3.0,
+            },
+            Noise::Bernoulli { p: 0.3 },
+        ];
+        for noise in distributions {
+            let samples: Vec<f64> = (0..100_000).map(|_| noise.sample(&mut a)).collect();
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+            let variance =
+                samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+            assert!((mean - noise.mean()).abs() < 0.03, "{:?}", noise);
+            assert!((variance - noise.variance()).abs() < 0.05, "{:?}", noise);
+        }
+    }
+}
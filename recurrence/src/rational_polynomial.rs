@@ -0,0 +1,697 @@
+use crate::polynomial::Polynomial;
+use num_rational::Ratio;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Signed, ToPrimitive, Zero};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// an exact rational number
+pub type Rational = Ratio<i128>;
+
+// largest denominator accepted when reading a float back as a rational
+const MAX_DENOMINATOR: i128 = 1_000_000;
+
+// largest number whose divisors are searched, which takes up to a million trial divisions
+const MAX_DIVISOR_SEARCH: i128 = 1_000_000_000_000;
+
+// largest number of candidate factors kronecker's method tries for a single degree
+const KRONECKER_BUDGET: usize = 1_000_000;
+
+/// returns the rational with a small denominator that the float was written as, if there is one
+/// for example 6.7 is read as 67/10, while an approximation of the golden ratio is not rational
+pub(crate) fn to_rational(x: f64) -> Option<Rational> {
+    if !x.is_finite() || x.abs() >= 9.0e15 {
+        return None;
+    }
+
+    // walk the convergents of the continued fraction of x until one rounds to exactly x
+    let (mut p0, mut q0, mut p1, mut q1) = (0i128, 1i128, 1i128, 0i128);
+    let mut remainder = x;
+    loop {
+        let a = remainder.floor();
+        let p2 = (a as i128).checked_mul(p1)?.checked_add(p0)?;
+        let q2 = (a as i128).checked_mul(q1)?.checked_add(q0)?;
+        if q2 > MAX_DENOMINATOR {
+            return None;
+        }
+        if p2 as f64 / q2 as f64 == x {
+            return Some(Rational::new(p2, q2));
+        }
+        let fractional = remainder - a;
+        if fractional == 0.0 {
+            return None;
+        }
+        remainder = 1.0 / fractional;
+        p0 = p1;
+        q0 = q1;
+        p1 = p2;
+        q1 = q2;
+    }
+}
+
+pub(crate) fn rational_to_f64(x: &Rational) -> f64 {
+    x.numer().to_f64().unwrap() / x.denom().to_f64().unwrap()
+}
+
+pub(crate) fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+fn lcm(a: i128, b: i128) -> Option<i128> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// returns the positive divisors of n, n must be nonzero
+// returns None if n is too large to search by trial division
+fn divisors(n: i128) -> Option<Vec<i128>> {
+    if n.checked_abs()? > MAX_DIVISOR_SEARCH {
+        return None;
+    }
+    let n = n.abs();
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    let mut d = 1;
+    while d <= n / d {
+        if n % d == 0 {
+            small.push(d);
+            if d != n / d {
+                large.push(n / d);
+            }
+        }
+        d += 1;
+    }
+    small.extend(large.into_iter().rev());
+    Some(small)
+}
+
+fn euler_phi(n: usize) -> usize {
+    let mut res = n;
+    let mut m = n;
+    let mut p = 2;
+    while p * p <= m {
+        if m % p == 0 {
+            while m % p == 0 {
+                m /= p;
+            }
+            res -= res / p;
+        }
+        p += 1;
+    }
+    if m > 1 {
+        res -= res / m;
+    }
+    res
+}
+
+/// a polynomial with exact rational coefficients
+/// coefficients start with the constant term, as in Polynomial
+/// the coefficients are fixed size rationals, so the exact algorithms return None when one of
+/// their operations overflows, while the arithmetic operators panic like those of the integers
+#[derive(Debug, PartialEq, Clone)]
+pub struct RationalPolynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl RationalPolynomial {
+    // creates a new polynomial from the given coefficients, starting with the constant term
+    pub fn new(mut coefficients: Vec<Rational>) -> RationalPolynomial {
+        // remove leading zero terms
+        while coefficients.len() > 1 && coefficients[coefficients.len() - 1].is_zero() {
+            coefficients.pop();
+        }
+        if coefficients.is_empty() {
+            coefficients.push(Rational::zero());
+        }
+        RationalPolynomial { coefficients }
+    }
+
+    pub fn from_integers(coefficients: &[i128]) -> RationalPolynomial {
+        RationalPolynomial::new(coefficients.iter().map(|c| Rational::from(*c)).collect())
+    }
+
+    // reads every coefficient of the polynomial back as a rational
+    // returns None if any coefficient is not a rational with a small denominator
+    pub fn from_polynomial(polynomial: &Polynomial) -> Option<RationalPolynomial> {
+        let coefficients = polynomial
+            .coefficients()
+            .iter()
+            .map(|c| to_rational(*c))
+            .collect::<Option<Vec<Rational>>>()?;
+        Some(RationalPolynomial::new(coefficients))
+    }
+
+    pub fn to_polynomial(&self) -> Polynomial {
+        Polynomial::new(self.coefficients.iter().map(rational_to_f64).collect())
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.len() == 1 && self.coefficients[0].is_zero()
+    }
+
+    pub fn leading_coefficient(&self) -> Rational {
+        self.coefficients[self.degree()]
+    }
+
+    pub fn evaluate(&self, x: Rational) -> Option<Rational> {
+        let mut res = Rational::zero();
+        for coefficient in self.coefficients.iter().rev() {
+            res = res.checked_mul(&x)?.checked_add(coefficient)?;
+        }
+        Some(res)
+    }
+
+    // returns the polynomial divided by its leading coefficient
+    pub fn monic(&self) -> Option<RationalPolynomial> {
+        if self.is_zero() {
+            return Some(self.clone());
+        }
+        let leading = self.leading_coefficient();
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|c| c.checked_div(&leading))
+            .collect::<Option<Vec<Rational>>>()?;
+        Some(RationalPolynomial::new(coefficients))
+    }
+
+    pub fn derivative(&self) -> Option<RationalPolynomial> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(power, c)| c.checked_mul(&Rational::from(power as i128)))
+            .collect::<Option<Vec<Rational>>>()?;
+        Some(RationalPolynomial::new(coefficients))
+    }
+
+    pub fn checked_add(&self, other: &RationalPolynomial) -> Option<RationalPolynomial> {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let mut coefficients = vec![Rational::zero(); len];
+        for (i, c) in self.coefficients.iter().enumerate() {
+            coefficients[i] = c.checked_add(&coefficients[i])?;
+        }
+        for (i, c) in other.coefficients.iter().enumerate() {
+            coefficients[i] = c.checked_add(&coefficients[i])?;
+        }
+        Some(RationalPolynomial::new(coefficients))
+    }
+
+    pub fn checked_sub(&self, other: &RationalPolynomial) -> Option<RationalPolynomial> {
+        self.checked_add(&-other)
+    }
+
+    pub fn checked_mul(&self, other: &RationalPolynomial) -> Option<RationalPolynomial> {
+        let mut coefficients =
+            vec![Rational::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j].checked_add(&a.checked_mul(b)?)?;
+            }
+        }
+        Some(RationalPolynomial::new(coefficients))
+    }
+
+    // returns the quotient and remainder of polynomial long division
+    pub fn div_rem(
+        &self,
+        divisor: &RationalPolynomial,
+    ) -> Option<(RationalPolynomial, RationalPolynomial)> {
+        if divisor.is_zero() {
+            panic!("division by the zero polynomial")
+        }
+        let mut remainder = self.coefficients.clone();
+        if self.degree() < divisor.degree() || self.is_zero() {
+            return Some((RationalPolynomial::new(vec![]), self.clone()));
+        }
+        let mut quotient = vec![Rational::zero(); self.degree() - divisor.degree() + 1];
+        let leading = divisor.leading_coefficient();
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor.degree()].checked_div(&leading)?;
+            quotient[i] = factor;
+            for (j, c) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] = remainder[i + j].checked_sub(&factor.checked_mul(c)?)?;
+            }
+        }
+        remainder.truncate(divisor.degree());
+        Some((
+            RationalPolynomial::new(quotient),
+            RationalPolynomial::new(remainder),
+        ))
+    }
+
+    // returns the monic greatest common divisor of the two polynomials
+    pub fn gcd(&self, other: &RationalPolynomial) -> Option<RationalPolynomial> {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            // keeping the remainders monic slows the growth of their coefficients
+            let r = a.div_rem(&b)?.1.monic()?;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    // returns the coefficients scaled to coprime integers with a positive leading coefficient
+    fn primitive_integers(&self) -> Option<Vec<i128>> {
+        let denominator = self
+            .coefficients
+            .iter()
+            .try_fold(1, |acc, c| lcm(acc, *c.denom()))?;
+        let integers = self
+            .coefficients
+            .iter()
+            .map(|c| Some(c.checked_mul(&Rational::from(denominator))?.to_integer()))
+            .collect::<Option<Vec<i128>>>()?;
+        let mut content = integers.iter().fold(0, |acc, c| gcd(acc, *c));
+        if content == 0 {
+            return Some(integers);
+        }
+        if integers[integers.len() - 1] < 0 {
+            content = -content;
+        }
+        Some(integers.iter().map(|c| c / content).collect())
+    }
+
+    // returns the distinct rational roots of the polynomial using the rational root test
+    // returns None if the exact arithmetic overflows or the constant or leading coefficient is too
+    // large to search for divisors
+    pub fn rational_roots(&self) -> Option<Vec<Rational>> {
+        let mut roots = Vec::new();
+        if self.is_zero() {
+            return Some(roots);
+        }
+        let integers = self.primitive_integers()?;
+        let lowest = integers.iter().position(|c| *c != 0).unwrap();
+        if lowest > 0 {
+            roots.push(Rational::zero());
+        }
+        let constant = integers[lowest];
+        let leading = integers[integers.len() - 1];
+        if lowest == integers.len() - 1 {
+            return Some(roots);
+        }
+        let leading_divisors = divisors(leading)?;
+        for p in divisors(constant)? {
+            for q in &leading_divisors {
+                let q = *q;
+                for candidate in &[Rational::new(p, q), Rational::new(-p, q)] {
+                    if !roots.contains(candidate) && self.evaluate(*candidate)?.is_zero() {
+                        roots.push(*candidate);
+                    }
+                }
+            }
+        }
+        Some(roots)
+    }
+
+    // splits the polynomial into monic squarefree parts using yun's algorithm
+    // each part is paired with the multiplicity shared by all of its roots
+    pub fn square_free_factorization(&self) -> Option<Vec<(RationalPolynomial, usize)>> {
+        let mut parts = Vec::new();
+        if self.degree() == 0 {
+            return Some(parts);
+        }
+        let f = self.monic()?;
+        let derivative = f.derivative()?;
+        let a = f.gcd(&derivative)?;
+        let mut b = f.div_rem(&a)?.0;
+        let c = derivative.div_rem(&a)?.0;
+        let mut d = c.checked_sub(&b.derivative()?)?;
+        let mut multiplicity = 1;
+        while b.degree() > 0 {
+            let a = b.gcd(&d)?;
+            b = b.div_rem(&a)?.0;
+            let c = d.div_rem(&a)?.0;
+            d = c.checked_sub(&b.derivative()?)?;
+            if a.degree() > 0 {
+                parts.push((a, multiplicity));
+            }
+            multiplicity += 1;
+        }
+        Some(parts)
+    }
+
+    // returns the monic factors of the polynomial over the rationals with their multiplicities
+    // linear factors come from the rational root test and the rest are split with kronecker's
+    // method, so the factors are irreducible unless kronecker's search for a factor of some degree
+    // would exceed a fixed budget, in which case that factor may be returned unsplit
+    // returns None if the exact arithmetic overflows or a coefficient is too large to search
+    pub fn factor(&self) -> Option<Vec<(RationalPolynomial, usize)>> {
+        let mut factors = Vec::new();
+        for (part, multiplicity) in self.square_free_factorization()? {
+            let mut remaining = part;
+            for root in remaining.rational_roots()? {
+                let linear = RationalPolynomial::new(vec![-root, Rational::one()]);
+                remaining = remaining.div_rem(&linear)?.0;
+                factors.push((linear, multiplicity));
+            }
+            if remaining.degree() > 0 {
+                for factor in kronecker_factor(&remaining)? {
+                    factors.push((factor, multiplicity));
+                }
+            }
+        }
+        // linear factors are ordered by decreasing root
+        factors.sort_by(|(a, _), (b, _)| {
+            a.degree().cmp(&b.degree()).then_with(|| {
+                if a.degree() == 1 && b.degree() == 1 {
+                    a.coefficients[0].cmp(&b.coefficients[0])
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+        });
+        Some(factors)
+    }
+
+    // returns the k-th cyclotomic polynomial
+    pub fn cyclotomic(k: usize) -> RationalPolynomial {
+        let mut coefficients = vec![0; k + 1];
+        coefficients[0] = -1;
+        coefficients[k] = 1;
+        let mut res = RationalPolynomial::from_integers(&coefficients);
+        for d in 1..k {
+            if k % d == 0 {
+                // the coefficients of cyclotomic polynomials are small integers
+                res = res
+                    .div_rem(&RationalPolynomial::cyclotomic(d))
+                    .expect("cyclotomic division overflowed")
+                    .0;
+            }
+        }
+        res
+    }
+
+    // returns k if the monic form of this polynomial is the k-th cyclotomic polynomial, whose roots
+    // are the primitive k-th roots of unity
+    pub fn cyclotomic_index(&self) -> Option<usize> {
+        let degree = self.degree();
+        if degree == 0 {
+            return None;
+        }
+        let monic = self.monic()?;
+        // phi(k) >= sqrt(k / 2), so only finitely many k have the right degree
+        (1..=2 * degree * degree + 2)
+            .filter(|k| euler_phi(*k) == degree)
+            .find(|k| RationalPolynomial::cyclotomic(*k) == monic)
+    }
+}
+
+// splits a monic squarefree polynomial with no rational roots into monic irreducible factors
+// returns None if the exact arithmetic overflows
+fn kronecker_factor(polynomial: &RationalPolynomial) -> Option<Vec<RationalPolynomial>> {
+    match kronecker_split(polynomial)? {
+        Some((g, h)) => {
+            let mut factors = kronecker_factor(&g)?;
+            factors.extend(kronecker_factor(&h)?);
+            Some(factors)
+        }
+        None => Some(vec![polynomial.clone()]),
+    }
+}
+
+fn evaluate_integers(coefficients: &[i128], x: i128) -> Option<i128> {
+    let mut res: i128 = 0;
+    for c in coefficients.iter().rev() {
+        res = res.checked_mul(x)?.checked_add(*c)?;
+    }
+    Some(res)
+}
+
+// the factors g and h of a polynomial gh found by kronecker's method
+type Split = (RationalPolynomial, RationalPolynomial);
+
+// finds a nontrivial factor of a polynomial with no rational roots by interpolating
+// through divisors of its values at integer points
+// returns Some(None) if there is no factor and None if the exact arithmetic overflows
+fn kronecker_split(polynomial: &RationalPolynomial) -> Option<Option<Split>> {
+    let degree = polynomial.degree();
+    // polynomials of degree two or three without rational roots are irreducible
+    if degree < 4 {
+        return Some(None);
+    }
+    let integers = polynomial.primitive_integers()?;
+
+    // sample integer points, preferring values with few divisors
+    let mut samples = Vec::new();
+    for i in 0..4 * degree as i128 {
+        let x = if i % 2 == 0 { -i / 2 } else { i / 2 + 1 };
+        if let Some(value) = evaluate_integers(&integers, x) {
+            if let Some(divisors) = divisors(value) {
+                samples.push((x, divisors));
+            }
+        }
+    }
+    samples.sort_by_key(|(_, d)| d.len());
+
+    for factor_degree in 2..=degree / 2 {
+        if samples.len() <= factor_degree {
+            break;
+        }
+        let points = &samples[..=factor_degree];
+        let mut total: usize = 1;
+        for (i, (_, d)) in points.iter().enumerate() {
+            let choices = if i == 0 { d.len() } else { 2 * d.len() };
+            total = total.saturating_mul(choices);
+        }
+        if total > KRONECKER_BUDGET {
+            continue;
+        }
+
+        // lagrange basis polynomials through the chosen points
+        let basis: Vec<RationalPolynomial> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (xi, _))| {
+                let mut res = RationalPolynomial::from_integers(&[1]);
+                for (j, (xj, _)) in points.iter().enumerate() {
+                    if i != j {
+                        let denominator = Rational::from(xi - xj);
+                        res = res.checked_mul(&RationalPolynomial::new(vec![
+                            Rational::from(-*xj) / denominator,
+                            Rational::one() / denominator,
+                        ]))?;
+                    }
+                }
+                Some(res)
+            })
+            .collect::<Option<Vec<RationalPolynomial>>>()?;
+
+        let mut choice = vec![0; points.len()];
+        'candidates: loop {
+            let mut candidate = RationalPolynomial::new(vec![]);
+            for (i, (_, d)) in points.iter().enumerate() {
+                // the first value is taken positive since g and -g are the same factor
+                let value = if choice[i] < d.len() {
+                    d[choice[i]]
+                } else {
+                    -d[choice[i] - d.len()]
+                };
+                let term = basis[i]
+                    .coefficients
+                    .iter()
+                    .map(|c| c.checked_mul(&Rational::from(value)))
+                    .collect::<Option<Vec<Rational>>>()?;
+                candidate = candidate.checked_add(&RationalPolynomial::new(term))?;
+            }
+            if candidate.degree() == factor_degree
+                && candidate.coefficients.iter().all(|c| c.is_integer())
+            {
+                let (quotient, remainder) = polynomial.div_rem(&candidate)?;
+                if remainder.is_zero() {
+                    return Some(Some((candidate.monic()?, quotient.monic()?)));
+                }
+            }
+
+            // advance the mixed radix counter over divisor choices
+            for i in 0..choice.len() {
+                let choices = if i == 0 {
+                    points[i].1.len()
+                } else {
+                    2 * points[i].1.len()
+                };
+                choice[i] += 1;
+                if choice[i] < choices {
+                    continue 'candidates;
+                }
+                choice[i] = 0;
+            }
+            break;
+        }
+    }
+    Some(None)
+}
+
+impl Add for &RationalPolynomial {
+    type Output = RationalPolynomial;
+
+    fn add(self, other: &RationalPolynomial) -> RationalPolynomial {
+        self.checked_add(other)
+            .expect("rational polynomial addition overflowed")
+    }
+}
+
+impl Neg for &RationalPolynomial {
+    type Output = RationalPolynomial;
+
+    fn neg(self) -> RationalPolynomial {
+        RationalPolynomial::new(self.coefficients.iter().map(|c| -c).collect())
+    }
+}
+
+impl Sub for &RationalPolynomial {
+    type Output = RationalPolynomial;
+
+    fn sub(self, other: &RationalPolynomial) -> RationalPolynomial {
+        self.checked_sub(other)
+            .expect("rational polynomial subtraction overflowed")
+    }
+}
+
+impl Mul for &RationalPolynomial {
+    type Output = RationalPolynomial;
+
+    fn mul(self, other: &RationalPolynomial) -> RationalPolynomial {
+        self.checked_mul(other)
+            .expect("rational polynomial multiplication overflowed")
+    }
+}
+
+impl fmt::Display for RationalPolynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut res = String::new();
+        for (pow, coefficient) in self.coefficients.iter().enumerate().rev() {
+            if coefficient.is_zero() {
+                continue;
+            }
+            if res.is_empty() {
+                if coefficient.is_negative() {
+                    res.push('-');
+                }
+            } else if coefficient.is_negative() {
+                res.push_str(" - ");
+            } else {
+                res.push_str(" + ");
+            }
+            let magnitude = coefficient.abs();
+            if pow == 0 || !magnitude.is_one() {
+                res.push_str(&magnitude.to_string());
+            }
+            if pow == 1 {
+                res.push('x');
+            } else if pow > 1 {
+                res.push_str(&format!("x^{}", pow));
+            }
+        }
+        write!(f, "{}", res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_rational() {
+        assert_eq!(to_rational(2.0), Some(Rational::from(2)));
+        assert_eq!(to_rational(-0.5), Some(Rational::new(-1, 2)));
+        assert_eq!(to_rational(6.7), Some(Rational::new(67, 10)));
+        assert_eq!(to_rational(1.0 / 3.0), Some(Rational::new(1, 3)));
+        assert_eq!(to_rational(1.618_033_988_749_895), None);
+        assert_eq!(to_rational(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_rational_roots() {
+        // (x - 2)(x + 1)(2x - 1)
+        let polynomial = RationalPolynomial::from_integers(&[2, -3, -3, 2]);
+        let mut roots = polynomial.rational_roots().unwrap();
+        roots.sort();
+        assert_eq!(
+            roots,
+            vec![Rational::from(-1), Rational::new(1, 2), Rational::from(2)]
+        );
+
+        let polynomial = RationalPolynomial::from_integers(&[-1, -1, 1]);
+        assert!(polynomial.rational_roots().unwrap().is_empty());
+
+        // the divisors of a constant this large are not searched
+        let polynomial = RationalPolynomial::from_integers(&[-1_000_000_000_000_000, 0, 1]);
+        assert_eq!(polynomial.rational_roots(), None);
+        assert_eq!(polynomial.factor(), None);
+    }
+
+    #[test]
+    fn test_factor() {
+        // x^4 - 1 = (x - 1)(x + 1)(x^2 + 1)
+        let polynomial = RationalPolynomial::from_integers(&[-1, 0, 0, 0, 1]);
+        let factors = polynomial.factor().unwrap();
+        assert_eq!(factors.len(), 3);
+        assert!(factors.contains(&(RationalPolynomial::from_integers(&[-1, 1]), 1)));
+        assert!(factors.contains(&(RationalPolynomial::from_integers(&[1, 1]), 1)));
+        assert!(factors.contains(&(RationalPolynomial::from_integers(&[1, 0, 1]), 1)));
+
+        // (x^2 - x - 1)^2 (x - 2)
+        let golden = RationalPolynomial::from_integers(&[-1, -1, 1]);
+        let polynomial = &(&golden * &golden) * &RationalPolynomial::from_integers(&[-2, 1]);
+        assert_eq!(
+            polynomial.factor(),
+            Some(vec![
+                (RationalPolynomial::from_integers(&[-2, 1]), 1),
+                (golden, 2)
+            ])
+        );
+
+        // x^4 + 4 has no rational roots but splits into two quadratics
+        let polynomial = RationalPolynomial::from_integers(&[4, 0, 0, 0, 1]);
+        let factors = polynomial.factor().unwrap();
+        assert_eq!(factors.len(), 2);
+        assert!(factors.contains(&(RationalPolynomial::from_integers(&[2, 2, 1]), 1)));
+        assert!(factors.contains(&(RationalPolynomial::from_integers(&[2, -2, 1]), 1)));
+    }
+
+    #[test]
+    fn test_cyclotomic_index() {
+        assert_eq!(
+            RationalPolynomial::from_integers(&[1, 1, 1]).cyclotomic_index(),
+            Some(3)
+        );
+        assert_eq!(
+            RationalPolynomial::from_integers(&[1, 0, 0, -1, 0, 0, 1]).cyclotomic_index(),
+            Some(18)
+        );
+        assert_eq!(
+            RationalPolynomial::from_integers(&[-1, -1, 1]).cyclotomic_index(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let polynomial = RationalPolynomial::new(vec![
+            Rational::new(-1, 2),
+            Rational::from(-1),
+            Rational::zero(),
+            Rational::from(1),
+        ]);
+        assert_eq!(polynomial.to_string(), "x^3 - x - 1/2");
+    }
+}
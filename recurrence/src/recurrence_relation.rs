@@ -1,11 +1,15 @@
+use crate::berlekamp_massey::minimal_relation;
+use crate::exact_solution::ExactSolution;
 use crate::polynomial::Polynomial;
+use crate::rational_polynomial::{to_rational, RationalPolynomial};
 use crate::recurrence_relation_parser::{parse_recurrence_relation, ParseRecurrenceError};
 use crate::recurrence_solution::RecurrenceSolution;
 use nalgebra::DMatrix;
 use std::cmp::min;
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct RecurrenceRelation {
     base_cases: Vec<f64>,
     recurrence_coefficients: Vec<f64>,
@@ -32,6 +36,16 @@ impl RecurrenceRelation {
         self.base_cases.len()
     }
 
+    /// returns the base cases f(0), f(1), ... of the recurrence
+    pub fn base_cases(&self) -> &[f64] {
+        &self.base_cases
+    }
+
+    /// returns the coefficients of f(n-1), f(n-2), ... in the recurrence
+    pub fn recurrence_coefficients(&self) -> &[f64] {
+        &self.recurrence_coefficients
+    }
+
     /// returns the characteristic polynomial of the recurrence
     pub fn characteristic_polynomial(&self) -> Polynomial {
         let mut coefficients = Vec::new();
@@ -45,37 +59,81 @@ impl RecurrenceRelation {
 
     /// returns the polynomial which is an explicit solution to the recurrence relation
     pub fn solve(&self) -> RecurrenceSolution {
+        // the zero sequence has no terms in its solution
+        if self.degree() == 0 {
+            return RecurrenceSolution::new(vec![]);
+        }
         let roots = self.characteristic_polynomial().roots();
+        let constants = self.solve_constants(&roots);
+        general_solution(&roots, &constants)
+    }
+
+    // returns the matrix of the linear system for the constants of the general solution
+    // there is a row for each base case f(n) and a column for each n^i r^n, grouped by root
+    pub(crate) fn base_case_matrix(&self, roots: &[(f64, usize)]) -> DMatrix<f64> {
         let mut elements = Vec::new();
         for n in 0..self.degree() {
-            for (root, count) in &roots {
+            for (root, count) in roots {
                 for i in 0..*count {
                     elements.push(root.powf(n as f64) * (n as f64).powf(i as f64));
                 }
             }
         }
+        DMatrix::from_row_slice(self.degree(), self.degree(), &elements)
+    }
 
-        let matrix = DMatrix::from_row_slice(self.degree(), self.degree(), &elements);
+    // returns the constants of the general solution which match the base cases
+    pub(crate) fn solve_constants(&self, roots: &[(f64, usize)]) -> Vec<f64> {
+        let matrix = self.base_case_matrix(roots);
         let base_cases_vec = DMatrix::from_row_slice(self.degree(), 1, &self.base_cases);
 
         let alphas_matrix = matrix
             .lu()
             .solve(&base_cases_vec)
             .expect("cant solve given linear system");
-        let alphas: Vec<f64> = alphas_matrix.iter().copied().collect();
+        alphas_matrix.iter().copied().collect()
+    }
 
-        let mut terms = Vec::new();
-        let mut index = 0;
-        for (root, count) in &roots {
-            let mut polynomial_coefficients = Vec::new();
-            for _ in 0..*count {
-                polynomial_coefficients.push(alphas[index]);
-                index += 1;
-            }
-            let polynomial = Polynomial::new(polynomial_coefficients);
-            terms.push((polynomial, *root));
+    /// returns the solution to the recurrence relation written exactly with radicals
+    /// returns None unless the coefficients and base cases are rational and every irreducible factor
+    /// of the characteristic polynomial has degree at most four
+    pub fn solve_exact(&self) -> Option<ExactSolution> {
+        let characteristic_polynomial =
+            RationalPolynomial::from_polynomial(&self.characteristic_polynomial())?;
+        let base_cases = self
+            .base_cases
+            .iter()
+            .map(|x| to_rational(*x))
+            .collect::<Option<Vec<_>>>()?;
+        ExactSolution::solve(&characteristic_polynomial, &base_cases)
+    }
+
+    /// returns the recurrence relation of smallest degree which generates the same sequence
+    /// for example f(n) = 3f(n-1) - 2f(n-2) with f(0) = 1 and f(1) = 2 minimizes to f(n) = 2f(n-1)
+    pub fn minimize(&self) -> RecurrenceRelation {
+        // a sequence of order at most d is determined by its first 2d terms
+        minimal_relation(&self.get_terms(2 * self.degree()))
+    }
+
+    /// returns the recurrence relation whose terms are the partial sums S(n) = f(0) + ... + f(n)
+    /// the characteristic polynomial of the result is the characteristic polynomial times (x - 1)
+    pub fn partial_sums(&self) -> RecurrenceRelation {
+        // S(n) - S(n-1) = f(n) satisfies the original recurrence
+        let degree = self.degree();
+        let mut recurrence_coefficients = vec![0.0; degree + 1];
+        recurrence_coefficients[0] = 1.0;
+        for (i, coefficient) in self.recurrence_coefficients.iter().enumerate() {
+            recurrence_coefficients[i] += coefficient;
+            recurrence_coefficients[i + 1] -= coefficient;
+        }
+
+        let mut base_cases = Vec::new();
+        let mut sum = 0.0;
+        for term in self.get_terms(degree + 1) {
+            sum += term;
+            base_cases.push(sum);
         }
-        RecurrenceSolution::new(terms)
+        RecurrenceRelation::new(base_cases, recurrence_coefficients)
     }
 
     /// returns the first n terms of the recurrence relation
@@ -98,6 +156,62 @@ impl RecurrenceRelation {
     }
 }
 
+// returns the solution with the constants multiplying n^i r^n, grouped by root
+pub(crate) fn general_solution(roots: &[(f64, usize)], constants: &[f64]) -> RecurrenceSolution {
+    let mut terms = Vec::new();
+    let mut index = 0;
+    for (root, count) in roots {
+        let mut polynomial_coefficients = Vec::new();
+        for _ in 0..*count {
+            polynomial_coefficients.push(constants[index]);
+            index += 1;
+        }
+        let polynomial = Polynomial::new(polynomial_coefficients);
+        terms.push((polynomial, *root));
+    }
+    RecurrenceSolution::new(terms)
+}
+
+/// writes the relation in the form accepted by FromStr, for example
+/// f(n) = f(n-1) + f(n-2), f(0) = 0, f(1) = 1
+/// zero coefficients are skipped except for the last, which fixes the degree
+impl fmt::Display for RecurrenceRelation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "f(n) =")?;
+        if self.degree() == 0 {
+            write!(f, " 0")?;
+        }
+        let mut first = true;
+        for (i, coefficient) in self.recurrence_coefficients.iter().enumerate() {
+            if *coefficient == 0.0 && i + 1 < self.degree() {
+                continue;
+            }
+            let magnitude = coefficient.abs();
+            if first {
+                let sign = if coefficient.is_sign_negative() && magnitude != 0.0 {
+                    "-"
+                } else {
+                    ""
+                };
+                write!(f, " {}", sign)?;
+            } else if coefficient.is_sign_negative() && magnitude != 0.0 {
+                write!(f, " - ")?;
+            } else {
+                write!(f, " + ")?;
+            }
+            if magnitude != 1.0 {
+                write!(f, "{}", magnitude)?;
+            }
+            write!(f, "f(n-{})", i + 1)?;
+            first = false;
+        }
+        for (i, base_case) in self.base_cases.iter().enumerate() {
+            write!(f, ", f({}) = {}", i, base_case)?;
+        }
+        Ok(())
+    }
+}
+
 impl FromStr for RecurrenceRelation {
     type Err = ParseRecurrenceError;
 
@@ -143,6 +257,87 @@ mod tests {
             recurrence_relation2.solve().get_terms(5),
             recurrence_relation2.get_terms(5)
         ));
+
+        // the exact factoring of this polynomial overflows, so its roots are found numerically
+        let recurrence_relation3 = RecurrenceRelation::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            vec![0.123457, 0.234567, 0.345679, 0.456791, 0.1, 0.3],
+        );
+        assert_eq!(
+            recurrence_relation3.characteristic_polynomial().factor(),
+            None
+        );
+        let roots = recurrence_relation3.characteristic_polynomial().roots();
+        assert_eq!(roots.iter().map(|(_, count)| count).sum::<usize>(), 6);
+        assert_eq!(recurrence_relation3.solve().get_terms(1).len(), 1);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let recurrence_relation1 = RecurrenceRelation::new(vec![1.0, 2.0], vec![3.0, -2.0]);
+        assert_eq!(
+            recurrence_relation1.minimize(),
+            RecurrenceRelation::new(vec![1.0], vec![2.0])
+        );
+
+        let recurrence_relation2 = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(recurrence_relation2.minimize(), recurrence_relation2);
+
+        // (x - 1)(x - 2)(x - 3) with base cases which only excite the root 3
+        let recurrence_relation3 =
+            RecurrenceRelation::new(vec![2.0, 6.0, 18.0], vec![6.0, -11.0, 6.0]);
+        let minimized3 = recurrence_relation3.minimize();
+        assert_eq!(minimized3, RecurrenceRelation::new(vec![2.0], vec![3.0]));
+        assert!(vec_within(
+            minimized3.solve().get_terms(10),
+            recurrence_relation3.get_terms(10)
+        ));
+    }
+
+    #[test]
+    fn test_partial_sums() {
+        let recurrence_relation1 = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let partial_sums1 = recurrence_relation1.partial_sums();
+        assert_eq!(
+            partial_sums1,
+            RecurrenceRelation::new(vec![0.0, 1.0, 2.0], vec![2.0, 0.0, -1.0])
+        );
+        assert_eq!(
+            partial_sums1.get_terms(10),
+            vec![0.0, 1.0, 2.0, 4.0, 7.0, 12.0, 20.0, 33.0, 54.0, 88.0]
+        );
+        assert_eq!(
+            partial_sums1.characteristic_polynomial(),
+            Polynomial::new(vec![1.0, 0.0, -2.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let recurrence_relation1 = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(
+            recurrence_relation1.to_string(),
+            "f(n) = f(n-1) + f(n-2), f(0) = 0, f(1) = 1"
+        );
+
+        let recurrence_relation2 =
+            RecurrenceRelation::new(vec![1.0, -2.0, 3.5], vec![-1.0, 0.0, -0.25]);
+        assert_eq!(
+            recurrence_relation2.to_string(),
+            "f(n) = -f(n-1) - 0.25f(n-3), f(0) = 1, f(1) = -2, f(2) = 3.5"
+        );
+
+        let recurrence_relations = vec![
+            recurrence_relation1,
+            recurrence_relation2,
+            RecurrenceRelation::new(vec![1.0, 2.0], vec![3.0, 0.0]),
+            RecurrenceRelation::new(vec![0.1, 1e-7, 1e20], vec![-1e-9, 6.7, 1.0 / 3.0]),
+            RecurrenceRelation::new(vec![], vec![]),
+        ];
+        for recurrence_relation in recurrence_relations {
+            let parsed: RecurrenceRelation = recurrence_relation.to_string().parse().unwrap();
+            assert_eq!(parsed, recurrence_relation);
+        }
     }
 
     #[test]
@@ -2,6 +2,7 @@ use crate::recurrence_relation::RecurrenceRelation;
 use std::cmp::max;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseRecurrenceError {
     NoRecurrence,
     MultipleRecurrence,
@@ -12,6 +13,7 @@ pub enum ParseRecurrenceError {
     ParseFloatError,
     ParseIntError,
     BaseCaseError,
+    BaseCaseOutOfRange,
     RecurrenceError,
 }
 
@@ -42,6 +44,94 @@ fn parse_base_case(s: &str) -> Result<(f64, usize), ParseRecurrenceError> {
     Ok((val, index))
 }
 
+// splits the right side of a recurrence into terms, each keeping the sign in front of it
+fn split_terms(s: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '+' | '-' if depth == 0 => {
+                let current = s[start..i].trim();
+                // a sign directly after another sign, a '*' or an exponent belongs to the current term
+                let continues = current.is_empty()
+                    || current.ends_with(['+', '-', '*'])
+                    || (current.ends_with(['e', 'E'])
+                        && current[..current.len() - 1].ends_with(|p: char| p.is_ascii_digit()));
+                if !continues {
+                    terms.push(&s[start..i]);
+                    start = i;
+                }
+            }
+            _ => {}
+        }
+    }
+    terms.push(&s[start..]);
+    terms
+}
+
+// parses a term such as -2.5f(n-3) or + 3 * f(n-1) into its coefficient and index
+fn parse_term(s: &str) -> Result<(f64, usize), ParseRecurrenceError> {
+    let mut sign = 1.0;
+    let mut rest = s.trim();
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '+' => rest = rest[1..].trim_start(),
+            '-' => {
+                sign = -sign;
+                rest = rest[1..].trim_start();
+            }
+            _ => break,
+        }
+    }
+
+    let lparen_index = rest
+        .find('(')
+        .ok_or(ParseRecurrenceError::RecurrenceError)?;
+    let rparen_index = rest
+        .rfind(')')
+        .ok_or(ParseRecurrenceError::RecurrenceError)?;
+    if rparen_index < lparen_index || !rest[rparen_index + 1..].trim().is_empty() {
+        return Err(ParseRecurrenceError::RecurrenceError);
+    }
+
+    let mut argument = rest[lparen_index + 1..rparen_index].split('-');
+    let _variable = argument
+        .next()
+        .ok_or(ParseRecurrenceError::RecurrenceError)?;
+    let index: usize = argument
+        .next()
+        .ok_or(ParseRecurrenceError::RecurrenceError)?
+        .trim()
+        .parse()?;
+    if argument.next().is_some() || index == 0 {
+        return Err(ParseRecurrenceError::RecurrenceError);
+    }
+
+    // the function name is the run of letters directly before the parenthesis
+    let before = rest[..lparen_index].trim_end();
+    let name_length = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .count();
+    if name_length == 0 {
+        return Err(ParseRecurrenceError::RecurrenceError);
+    }
+    let coefficient = before[..before.len() - name_length]
+        .trim()
+        .trim_end_matches('*')
+        .trim();
+    let coefficient: f64 = if coefficient.is_empty() {
+        1.0
+    } else {
+        coefficient.parse()?
+    };
+    Ok((sign * coefficient, index))
+}
+
 fn parse_recurrence(s: &str) -> Result<Vec<f64>, ParseRecurrenceError> {
     let mut parts = s.split('=');
     //todo: check that left has correct format, return error if it does not
@@ -51,40 +141,22 @@ fn parse_recurrence(s: &str) -> Result<Vec<f64>, ParseRecurrenceError> {
         return Err(ParseRecurrenceError::RecurrenceError);
     }
 
-    //todo: support minus sign as separator as well
-    let parts = right.split('+');
+    // the zero sequence satisfies a recurrence of degree 0
+    if let Ok(0.0) = right.trim().parse::<f64>() {
+        return Ok(Vec::new());
+    }
+
     let mut degree = 0;
     let mut pairs: Vec<(f64, usize)> = Vec::new();
-    for part in parts {
-        let part = part.trim();
-        let lparen_index = part
-            .find('(')
-            .ok_or(ParseRecurrenceError::RecurrenceError)?;
-        let rparen_index = part
-            .find(')')
-            .ok_or(ParseRecurrenceError::RecurrenceError)?;
-        let minus_index = part
-            .find('-')
-            .ok_or(ParseRecurrenceError::RecurrenceError)?;
-
-        let index: usize = part[minus_index + 1..rparen_index].trim().parse()?;
+    for part in split_terms(right) {
+        let (coefficient, index) = parse_term(part)?;
         degree = max(degree, index);
-        match (lparen_index, part[..lparen_index - 1].trim().parse()) {
-            (1, _) => pairs.push((1.0, index)),
-            (_, Ok(coefficient)) => pairs.push((coefficient, index)),
-            _ => {
-                let times_index = part
-                    .find('*')
-                    .ok_or(ParseRecurrenceError::RecurrenceError)?;
-                let coefficient = part[..times_index].trim().parse()?;
-                pairs.push((coefficient, index))
-            }
-        }
+        pairs.push((coefficient, index));
     }
 
     let mut res = vec![0.0; degree];
     for (coefficient, index) in pairs {
-        res[index - 1] = coefficient;
+        res[index - 1] += coefficient;
     }
     Ok(res)
 }
@@ -112,6 +184,9 @@ pub fn parse_recurrence_relation(s: &str) -> Result<RecurrenceRelation, ParseRec
     let degree = recurrence.len();
     let mut base_cases = vec![None; degree];
     for (num, index) in base_case_pairs {
+        if index >= degree {
+            return Err(ParseRecurrenceError::BaseCaseOutOfRange);
+        }
         if base_cases[index].is_some() {
             return Err(ParseRecurrenceError::MultipleBaseCase);
         } else {
@@ -158,6 +233,21 @@ mod tests {
             parse_recurrence("f(n) = 3*f(n-1) + 5*f(n-3) + 10.67*f(n-6)").unwrap(),
             vec![3.0, 0.0, 5.0, 0.0, 0.0, 10.67]
         );
+        assert_eq!(
+            parse_recurrence("f(n) = 3f(n-1) - 2f(n-2)").unwrap(),
+            vec![3.0, -2.0]
+        );
+        assert_eq!(
+            parse_recurrence("f(n) = -f(n-1) - -0.5 * f(n-2) + 1e-7f(n-3)").unwrap(),
+            vec![-1.0, 0.5, 1e-7]
+        );
+        assert_eq!(
+            parse_recurrence("f(n) = f(n-1) + 0f(n-2)").unwrap(),
+            vec![1.0, 0.0]
+        );
+        assert_eq!(parse_recurrence("f(n) = 0").unwrap(), Vec::<f64>::new());
+        assert!(parse_recurrence("f(n) = 2").is_err());
+        assert!(parse_recurrence("f(n) = f(n)").is_err());
     }
 
     #[test]
@@ -177,5 +267,8 @@ mod tests {
             relation,
             RecurrenceRelation::new(vec![1.0, 4.0, 3.0, 5.0], vec![0.0, 0.0, 6.7, 5.0])
         );
+        assert!("f(n) = f(n-1), f(0) = 1, f(1) = 2"
+            .parse::<RecurrenceRelation>()
+            .is_err());
     }
 }
@@ -1,16 +1,67 @@
+use crate::exact_solution::ExactSolution;
+use crate::format::FormatOptions;
+use crate::identify::identify_constant;
 use crate::polynomial::Polynomial;
 use std::fmt;
 
+// distance within which a root is taken to be exactly 0 or 1, near the rounding error of the
+// roots, since a root merely close to 1 still needs the geometric sum
+const EXACT_ROOT_TOLERANCE: f64 = 1e-12;
+
 #[derive(Debug, Clone)]
 pub struct RecurrenceSolution {
     terms: Vec<(Polynomial, f64)>,
 }
 
+fn binomial(n: usize, k: usize) -> f64 {
+    let mut res = 1.0;
+    for i in 0..k {
+        res = res * (n - i) as f64 / (i + 1) as f64;
+    }
+    res
+}
+
+// returns the coefficients of Q(n) = P(0) + P(1) + ... + P(n) for the polynomial P
+fn polynomial_sum(p: &[f64]) -> Vec<f64> {
+    // Q(n) - Q(n-1) = P(n), solved from the highest power down
+    let mut q = vec![0.0; p.len() + 1];
+    for j in (0..p.len()).rev() {
+        let mut rest = p[j];
+        for (i, coefficient) in q.iter().enumerate().skip(j + 2) {
+            let sign = if (i - j) % 2 == 0 { -1.0 } else { 1.0 };
+            rest -= coefficient * binomial(i, j) * sign;
+        }
+        q[j + 1] = rest / (j + 1) as f64;
+    }
+    q[0] = p[0];
+    q
+}
+
+// returns the coefficients of Q with P(0)r^0 + ... + P(n)r^n = Q(n)r^n + C for a root r other than 0 and 1
+fn geometric_sum(p: &[f64], root: f64) -> Vec<f64> {
+    // Q(n) - Q(n-1)/r = P(n), solved from the highest power down
+    let mut q = vec![0.0; p.len()];
+    for j in (0..p.len()).rev() {
+        let mut rest = p[j];
+        for (i, coefficient) in q.iter().enumerate().skip(j + 1) {
+            let sign = if (i - j) % 2 == 0 { 1.0 } else { -1.0 };
+            rest += coefficient * binomial(i, j) * sign / root;
+        }
+        q[j] = rest / (1.0 - 1.0 / root);
+    }
+    q
+}
+
 impl RecurrenceSolution {
     pub fn new(terms: Vec<(Polynomial, f64)>) -> RecurrenceSolution {
         RecurrenceSolution { terms }
     }
 
+    /// returns the terms of the solution as pairs of a polynomial in n and the root it multiplies
+    pub fn terms(&self) -> &[(Polynomial, f64)] {
+        &self.terms
+    }
+
     fn evaluate(&self, n: u32) -> f64 {
         let mut res = 0.0;
         for (polynomial, root) in &self.terms {
@@ -26,20 +77,63 @@ impl RecurrenceSolution {
         }
         res
     }
+
+    /// returns the closed form of the partial sums S(n) = f(0) + ... + f(n)
+    /// each polynomial times root^n term is summed as a geometric sum with a polynomial factor
+    pub fn partial_sums(&self) -> RecurrenceSolution {
+        let mut constant = 0.0;
+        let mut unit_coefficients = vec![0.0];
+        let mut terms = Vec::new();
+        for (polynomial, root) in &self.terms {
+            let p = polynomial.coefficients();
+            if root.abs() <= EXACT_ROOT_TOLERANCE {
+                // P(n)0^n only contributes P(0) at n = 0
+                constant += p[0];
+            } else if (root - 1.0).abs() <= EXACT_ROOT_TOLERANCE {
+                let q = polynomial_sum(p);
+                if q.len() > unit_coefficients.len() {
+                    unit_coefficients.resize(q.len(), 0.0);
+                }
+                for (i, coefficient) in q.iter().enumerate() {
+                    unit_coefficients[i] += coefficient;
+                }
+            } else {
+                let q = geometric_sum(p, *root);
+                constant += p[0] - q[0];
+                terms.push((Polynomial::new(q), *root));
+            }
+        }
+        unit_coefficients[0] += constant;
+        if unit_coefficients.iter().any(|c| *c != 0.0) {
+            terms.push((Polynomial::new(unit_coefficients), 1.0));
+        }
+        RecurrenceSolution::new(terms)
+    }
+
+    /// recognises the coefficients and roots of the solution as closed-form numbers
+    /// returns None if any of them is not recognised
+    pub fn identify(&self) -> Option<ExactSolution> {
+        let mut terms = Vec::new();
+        for (polynomial, root) in &self.terms {
+            let coefficients = polynomial
+                .coefficients()
+                .iter()
+                .map(|c| identify_constant(*c))
+                .collect::<Option<Vec<_>>>()?;
+            terms.push((coefficients, identify_constant(*root)?));
+        }
+        Some(ExactSolution::new(terms))
+    }
 }
 
+// writes the solution with the default format options and the precision of the format string
 impl fmt::Display for RecurrenceSolution {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut res = String::new();
-
-        for (i, (polynomial, root)) in self.terms.iter().enumerate() {
-            if i == self.terms.len() - 1 {
-                res.push_str(&format!("({}) * {:.3}^n", polynomial, root));
-            } else {
-                res.push_str(&format!("({}) * {:.3}^n + ", polynomial, root));
-            }
+        let mut options = FormatOptions::default();
+        if let Some(precision) = f.precision() {
+            options.precision = precision;
         }
-        write!(f, "{}", res)
+        write!(f, "{}", self.format(&options))
     }
 }
 
@@ -62,4 +156,67 @@ mod tests {
 
         // todo: add testcase with multiplicity greater than 1
     }
+
+    #[test]
+    fn test_partial_sums() {
+        let recurrence_solution1 = RecurrenceSolution::new(vec![
+            (Polynomial::new(vec![0.4472135955]), 1.618_033_988_749_895),
+            (
+                Polynomial::new(vec![-0.4472135955]),
+                -0.618_033_988_749_894_9,
+            ),
+        ]);
+        let sums1 = vec![0.0, 1.0, 2.0, 4.0, 7.0, 12.0, 20.0, 33.0, 54.0, 88.0];
+        assert!(vec_within(
+            recurrence_solution1.partial_sums().get_terms(10),
+            sums1
+        ));
+
+        // (1 + n^2) 2^n + 3n + 0^n
+        let recurrence_solution2 = RecurrenceSolution::new(vec![
+            (Polynomial::new(vec![1.0, 0.0, 1.0]), 2.0),
+            (Polynomial::new(vec![0.0, 3.0]), 1.0),
+            (Polynomial::new(vec![1.0]), 0.0),
+        ]);
+        let mut sums2 = Vec::new();
+        let mut sum = 0.0;
+        for term in recurrence_solution2.get_terms(10) {
+            sum += term;
+            sums2.push(sum);
+        }
+        assert!(vec_within(
+            recurrence_solution2.partial_sums().get_terms(10),
+            sums2
+        ));
+
+        // a root close to 1 is still summed as a geometric series
+        let recurrence_solution3 =
+            RecurrenceSolution::new(vec![(Polynomial::new(vec![1000.0]), 1.0005)]);
+        let sums3: Vec<f64> = (1..=10)
+            .map(|n| 1000.0 * (1.0005f64.powi(n) - 1.0) / 0.0005)
+            .collect();
+        assert!(vec_within(
+            recurrence_solution3.partial_sums().get_terms(10),
+            sums3
+        ));
+    }
+
+    #[test]
+    fn test_identify() {
+        let recurrence_solution1 = RecurrenceSolution::new(vec![
+            (Polynomial::new(vec![0.4472135955]), 1.618_033_988_749_895),
+            (
+                Polynomial::new(vec![-0.4472135955]),
+                -0.618_033_988_749_894_9,
+            ),
+        ]);
+        assert_eq!(
+            recurrence_solution1.identify().unwrap().to_string(),
+            "(1/√5)((1+√5)/2)^n - (1/√5)((1-√5)/2)^n"
+        );
+
+        let recurrence_solution2 =
+            RecurrenceSolution::new(vec![(Polynomial::new(vec![0.5]), std::f64::consts::PI)]);
+        assert!(recurrence_solution2.identify().is_none());
+    }
 }
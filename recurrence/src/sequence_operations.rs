@@ -0,0 +1,197 @@
+use crate::berlekamp_massey::minimal_relation;
+use crate::recurrence_relation::RecurrenceRelation;
+use std::ops::{Add, Mul, Neg, Sub};
+
+impl RecurrenceRelation {
+    /// returns the minimal recurrence relation for the sequence f(n + k)
+    pub fn shift(&self, k: usize) -> RecurrenceRelation {
+        let bound = self.degree();
+        let terms = self.get_terms(k + 2 * bound);
+        minimal_relation(&terms[k..])
+    }
+
+    /// returns the minimal recurrence relation for the convolution (f * g)(n) = Σ f(k)g(n - k)
+    pub fn cauchy_product(&self, other: &RecurrenceRelation) -> RecurrenceRelation {
+        let bound = self.degree() + other.degree();
+        let f = self.get_terms(2 * bound);
+        let g = other.get_terms(2 * bound);
+        let terms: Vec<f64> = (0..2 * bound)
+            .map(|n| (0..=n).map(|k| f[k] * g[n - k]).sum())
+            .collect();
+        minimal_relation(&terms)
+    }
+
+    // combines the sequences termwise into a sequence of order at most bound
+    fn termwise(
+        &self,
+        other: &RecurrenceRelation,
+        bound: usize,
+        operation: impl Fn(f64, f64) -> f64,
+    ) -> RecurrenceRelation {
+        let terms: Vec<f64> = self
+            .get_terms(2 * bound)
+            .iter()
+            .zip(other.get_terms(2 * bound))
+            .map(|(x, y)| operation(*x, y))
+            .collect();
+        minimal_relation(&terms)
+    }
+}
+
+/// termwise sum of two sequences
+impl Add for &RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn add(self, other: &RecurrenceRelation) -> RecurrenceRelation {
+        self.termwise(other, self.degree() + other.degree(), |x, y| x + y)
+    }
+}
+
+/// termwise difference of two sequences
+impl Sub for &RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn sub(self, other: &RecurrenceRelation) -> RecurrenceRelation {
+        self + &(-other)
+    }
+}
+
+/// termwise (hadamard) product of two sequences
+impl Mul for &RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn mul(self, other: &RecurrenceRelation) -> RecurrenceRelation {
+        self.termwise(other, self.degree() * other.degree(), |x, y| x * y)
+    }
+}
+
+/// scalar multiple of a sequence
+impl Mul<f64> for &RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn mul(self, scalar: f64) -> RecurrenceRelation {
+        let terms: Vec<f64> = self
+            .get_terms(2 * self.degree())
+            .iter()
+            .map(|x| x * scalar)
+            .collect();
+        minimal_relation(&terms)
+    }
+}
+
+impl Neg for &RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn neg(self) -> RecurrenceRelation {
+        self * -1.0
+    }
+}
+
+impl Add for RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn add(self, other: RecurrenceRelation) -> RecurrenceRelation {
+        &self + &other
+    }
+}
+
+impl Sub for RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn sub(self, other: RecurrenceRelation) -> RecurrenceRelation {
+        &self - &other
+    }
+}
+
+impl Mul for RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn mul(self, other: RecurrenceRelation) -> RecurrenceRelation {
+        &self * &other
+    }
+}
+
+impl Mul<f64> for RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn mul(self, scalar: f64) -> RecurrenceRelation {
+        &self * scalar
+    }
+}
+
+impl Neg for RecurrenceRelation {
+    type Output = RecurrenceRelation;
+
+    fn neg(self) -> RecurrenceRelation {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::*;
+
+    #[test]
+    fn test_termwise_operations() {
+        let f = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let l = RecurrenceRelation::new(vec![2.0, 1.0], vec![1.0, 1.0]);
+
+        assert_eq!(
+            &f + &l,
+            RecurrenceRelation::new(vec![2.0, 2.0], vec![1.0, 1.0])
+        );
+        assert_eq!(
+            &f * &f,
+            RecurrenceRelation::new(vec![0.0, 1.0, 1.0], vec![2.0, 2.0, -1.0])
+        );
+        assert_eq!(
+            &f * 3.0,
+            RecurrenceRelation::new(vec![0.0, 3.0], vec![1.0, 1.0])
+        );
+        assert_eq!(&f - &f, RecurrenceRelation::new(vec![], vec![]));
+        assert_eq!((&f - &f).solve().get_terms(3), vec![0.0; 3]);
+        // F(n)L(n) = F(2n)
+        assert_eq!(
+            &f * &l,
+            RecurrenceRelation::new(vec![0.0, 1.0], vec![3.0, -1.0])
+        );
+
+        let combined = f.clone() * f.clone() + l.clone();
+        assert_eq!(combined.degree(), 5);
+        let expected: Vec<f64> = f
+            .get_terms(15)
+            .iter()
+            .zip(l.get_terms(15))
+            .map(|(x, y)| x * x + y)
+            .collect();
+        assert_eq!(combined.get_terms(15), expected);
+        assert!(vec_within(combined.solve().get_terms(15), expected));
+    }
+
+    #[test]
+    fn test_cauchy_product() {
+        let f = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let product = f.cauchy_product(&f);
+        assert_eq!(product.degree(), 4);
+        assert_eq!(
+            product.get_terms(8),
+            vec![0.0, 0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 38.0]
+        );
+    }
+
+    #[test]
+    fn test_shift() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(
+            fibonacci.shift(2),
+            RecurrenceRelation::new(vec![1.0, 2.0], vec![1.0, 1.0])
+        );
+        // the shifted sequence 2, 4, 8, ... no longer needs the second root of (x - 2)(x - 0)
+        let relation = RecurrenceRelation::new(vec![3.0, 2.0], vec![2.0, 0.0]);
+        assert_eq!(
+            relation.shift(1),
+            RecurrenceRelation::new(vec![2.0], vec![2.0])
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::berlekamp_massey::minimal_relation;
+use crate::recurrence_relation::RecurrenceRelation;
+
+fn binomial_rows(n: usize) -> Vec<Vec<f64>> {
+    let mut rows: Vec<Vec<f64>> = Vec::new();
+    for i in 0..n {
+        let mut row = vec![1.0; i + 1];
+        for k in 1..i {
+            row[k] = rows[i - 1][k - 1] + rows[i - 1][k];
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+impl RecurrenceRelation {
+    /// returns the minimal recurrence relation for f(kn + r), taking every k-th term starting at r
+    /// the characteristic roots of the result are the k-th powers of the original roots
+    pub fn decimate(&self, k: usize, r: usize) -> RecurrenceRelation {
+        if k == 0 {
+            panic!("decimation step must be positive")
+        }
+        let count = 2 * self.degree();
+        let terms = self.get_terms(k * count + r);
+        let decimated: Vec<f64> = (0..count).map(|n| terms[k * n + r]).collect();
+        minimal_relation(&decimated)
+    }
+
+    /// returns the minimal recurrence relation for the sequences interleaved term by term,
+    /// so g(mn + i) is the n-th term of the i-th of the m relations
+    pub fn interleave(relations: &[RecurrenceRelation]) -> RecurrenceRelation {
+        let m = relations.len();
+        let bound: usize = m * relations.iter().map(|r| r.degree()).sum::<usize>();
+        let per_relation = (2 * bound).div_ceil(m.max(1));
+        let terms: Vec<Vec<f64>> = relations
+            .iter()
+            .map(|r| r.get_terms(per_relation))
+            .collect();
+        let interleaved: Vec<f64> = (0..2 * bound).map(|n| terms[n % m][n / m]).collect();
+        minimal_relation(&interleaved)
+    }
+
+    /// returns the minimal recurrence relation for the binomial transform b(n) = Σ C(n, k) f(k)
+    /// the characteristic roots of the result are the original roots plus one
+    pub fn binomial_transform(&self) -> RecurrenceRelation {
+        self.binomial_transform_with_sign(1.0)
+    }
+
+    /// returns the minimal recurrence relation for the inverse binomial transform
+    /// f(n) = Σ (-1)^(n - k) C(n, k) b(k), whose characteristic roots are the original roots minus one
+    pub fn inverse_binomial_transform(&self) -> RecurrenceRelation {
+        self.binomial_transform_with_sign(-1.0)
+    }
+
+    fn binomial_transform_with_sign(&self, sign: f64) -> RecurrenceRelation {
+        let count = 2 * self.degree();
+        let terms = self.get_terms(count);
+        let transformed: Vec<f64> = binomial_rows(count)
+            .iter()
+            .enumerate()
+            .map(|(n, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(k, c)| sign.powi((n - k) as i32) * c * terms[k])
+                    .sum()
+            })
+            .collect();
+        minimal_relation(&transformed)
+    }
+
+    /// returns the minimal recurrence relation for g(n) = f(-n), running the recurrence backwards
+    /// the characteristic roots of the result are the reciprocals of the original roots
+    /// returns None if the last recurrence coefficient is zero, since the sequence then has no
+    /// unique extension to negative indices
+    pub fn reverse(&self) -> Option<RecurrenceRelation> {
+        let degree = self.degree();
+        let coefficients = self.recurrence_coefficients();
+        if degree > 0 && coefficients[degree - 1] == 0.0 {
+            return None;
+        }
+
+        // window holds f(n - degree + 1), ..., f(n) and slides back one index at a time
+        let mut window = self.get_terms(degree);
+        let mut reversed = Vec::new();
+        if degree > 0 {
+            reversed.push(window[0]);
+        }
+        for _ in 1..2 * degree {
+            // f(n) = a_1 f(n-1) + ... + a_d f(n-d), solved for f(n-d)
+            let mut rest = window[degree - 1];
+            for i in 1..degree {
+                rest -= coefficients[i - 1] * window[degree - 1 - i];
+            }
+            let earlier = rest / coefficients[degree - 1];
+            window.pop();
+            window.insert(0, earlier);
+            reversed.push(earlier);
+        }
+        Some(minimal_relation(&reversed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimate() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(
+            fibonacci.decimate(2, 0),
+            RecurrenceRelation::new(vec![0.0, 1.0], vec![3.0, -1.0])
+        );
+        assert_eq!(
+            fibonacci.decimate(3, 1),
+            RecurrenceRelation::new(vec![1.0, 3.0], vec![4.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_interleave() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let lucas = RecurrenceRelation::new(vec![2.0, 1.0], vec![1.0, 1.0]);
+        let interleaved = RecurrenceRelation::interleave(&[fibonacci, lucas]);
+        assert_eq!(interleaved.degree(), 4);
+        assert_eq!(
+            interleaved.get_terms(10),
+            vec![0.0, 2.0, 1.0, 1.0, 1.0, 3.0, 2.0, 4.0, 3.0, 7.0]
+        );
+    }
+
+    #[test]
+    fn test_binomial_transform() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let transformed = fibonacci.binomial_transform();
+        assert_eq!(transformed, fibonacci.decimate(2, 0));
+        assert_eq!(transformed.inverse_binomial_transform(), fibonacci);
+    }
+
+    #[test]
+    fn test_reverse() {
+        // F(-n) = (-1)^(n+1) F(n)
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        assert_eq!(
+            fibonacci.reverse().unwrap(),
+            RecurrenceRelation::new(vec![0.0, 1.0], vec![-1.0, 1.0])
+        );
+        let relation = RecurrenceRelation::new(vec![1.0, 2.0], vec![2.0, 0.0]);
+        assert_eq!(relation.reverse(), None);
+    }
+}
@@ -0,0 +1,313 @@
+// serde support for the public types, enabled by the serde feature
+// the schema below is stable, any change to it is a breaking change
+//
+// RecurrenceRelation
+//     {"base_cases": [0.0, 1.0], "coefficients": [1.0, 1.0]}
+//     base_cases are f(0), f(1), ... and coefficients multiply f(n-1), f(n-2), ...
+//     both have the degree of the recurrence as their length
+//
+// Polynomial
+//     {"coefficients": [-1.0, -1.0, 1.0]}
+//     starts with the constant term and has at least one coefficient
+//
+// RecurrenceSolution
+//     {"terms": [{"root": 2.0, "coefficients": [1.0, 3.0]}]}
+//     f(n) is the sum over the terms of (c_0 + c_1 n + ... ) root^n
+//     each term has at least one coefficient, and in solutions returned by solve a root of
+//     multiplicity m has exactly m, with zero coefficients kept, so the multiplicity of a root is
+//     the length of its coefficients
+//
+// RationalPolynomial
+//     {"coefficients": [[-1, 2], [0, 1], [1, 1]]}
+//     rationals are [numerator, denominator] pairs, starting with the constant term
+//     denominators must be nonzero, and the rationals are reduced when read
+//
+// StateSpace
+//     {"a": [[0.0, 1.0], [1.0, 1.0]], "b": [0.0, 1.0], "c": [1.0, 0.0], "d": 0.0,
+//      "initial_state": [0.0, 0.0]}
+//     a is a list of rows, and b, c and initial_state have one entry for each row
+//
+// Algebraic, ExactSolution, Equivalence, Asymptotics, Growth, Stability, MultistepMethod, Filter,
+// TransferFunction, ZTransform, ArModel, ArMethod, OrderCriterion, Forecast, Noise,
+// StochasticRecurrence, MonteCarlo, NonlinearRecurrence, IteratedMap, Cycle, ParseExpressionError,
+// EvaluationError and ParseRecurrenceError use serde's default externally tagged representation, so
+// for example ParseRecurrenceError::NoBaseCase is "NoBaseCase", and complex numbers are [re, im]
+// pairs
+
+use crate::polynomial::Polynomial;
+use crate::rational_polynomial::{Rational, RationalPolynomial};
+use crate::recurrence_relation::RecurrenceRelation;
+use crate::recurrence_solution::RecurrenceSolution;
+use crate::state_space::StateSpace;
+use nalgebra::{DMatrix, DVector, RowDVector};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecurrenceRelationSchema {
+    base_cases: Vec<f64>,
+    coefficients: Vec<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolynomialSchema<T> {
+    coefficients: Vec<T>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TermSchema {
+    root: f64,
+    coefficients: Vec<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecurrenceSolutionSchema {
+    terms: Vec<TermSchema>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StateSpaceSchema {
+    a: Vec<Vec<f64>>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+    d: f64,
+    initial_state: Vec<f64>,
+}
+
+impl Serialize for RecurrenceRelation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RecurrenceRelationSchema {
+            base_cases: self.base_cases().to_vec(),
+            coefficients: self.recurrence_coefficients().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecurrenceRelation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = RecurrenceRelationSchema::deserialize(deserializer)?;
+        if schema.base_cases.len() != schema.coefficients.len() {
+            return Err(D::Error::custom(
+                "base_cases and coefficients must have the same length",
+            ));
+        }
+        Ok(RecurrenceRelation::new(
+            schema.base_cases,
+            schema.coefficients,
+        ))
+    }
+}
+
+impl Serialize for Polynomial {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PolynomialSchema {
+            coefficients: self.coefficients().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Polynomial {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = PolynomialSchema::<f64>::deserialize(deserializer)?;
+        if schema.coefficients.is_empty() {
+            return Err(D::Error::custom(
+                "a polynomial needs at least one coefficient",
+            ));
+        }
+        Ok(Polynomial::new(schema.coefficients))
+    }
+}
+
+impl Serialize for RationalPolynomial {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PolynomialSchema {
+            coefficients: self
+                .coefficients()
+                .iter()
+                .map(|c| (*c.numer(), *c.denom()))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RationalPolynomial {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = PolynomialSchema::<(i128, i128)>::deserialize(deserializer)?;
+        let mut coefficients = Vec::new();
+        for (numerator, denominator) in schema.coefficients {
+            if denominator == 0 {
+                return Err(D::Error::custom("a rational needs a nonzero denominator"));
+            }
+            coefficients.push(Rational::new(numerator, denominator));
+        }
+        Ok(RationalPolynomial::new(coefficients))
+    }
+}
+
+impl Serialize for RecurrenceSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let terms = self
+            .terms()
+            .iter()
+            .map(|(polynomial, root)| TermSchema {
+                root: *root,
+                coefficients: polynomial.coefficients().to_vec(),
+            })
+            .collect();
+        RecurrenceSolutionSchema { terms }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecurrenceSolution {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = RecurrenceSolutionSchema::deserialize(deserializer)?;
+        let mut terms = Vec::new();
+        for term in schema.terms {
+            if term.coefficients.is_empty() {
+                return Err(D::Error::custom("a term needs at least one coefficient"));
+            }
+            terms.push((Polynomial::new(term.coefficients), term.root));
+        }
+        Ok(RecurrenceSolution::new(terms))
+    }
+}
+
+impl Serialize for StateSpace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StateSpaceSchema {
+            a: self
+                .a()
+                .row_iter()
+                .map(|row| row.iter().copied().collect())
+                .collect(),
+            b: self.b().iter().copied().collect(),
+            c: self.c().iter().copied().collect(),
+            d: self.d(),
+            initial_state: self.initial_state().iter().copied().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StateSpace {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = StateSpaceSchema::deserialize(deserializer)?;
+        let n = schema.a.len();
+        if schema.a.iter().any(|row| row.len() != n)
+            || schema.b.len() != n
+            || schema.c.len() != n
+            || schema.initial_state.len() != n
+        {
+            return Err(D::Error::custom(
+                "a must be square with b, c and initial_state the same size",
+            ));
+        }
+        let elements: Vec<f64> = schema.a.into_iter().flatten().collect();
+        Ok(StateSpace::new(
+            DMatrix::from_row_slice(n, n, &elements),
+            DVector::from_vec(schema.b),
+            RowDVector::from_vec(schema.c),
+            schema.d,
+            DVector::from_vec(schema.initial_state),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurrence_relation_parser::ParseRecurrenceError;
+
+    #[test]
+    fn test_recurrence_relation() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let json = serde_json::to_string(&fibonacci).unwrap();
+        assert_eq!(json, r#"{"base_cases":[0.0,1.0],"coefficients":[1.0,1.0]}"#);
+        assert_eq!(
+            serde_json::from_str::<RecurrenceRelation>(&json).unwrap(),
+            fibonacci
+        );
+        assert!(serde_json::from_str::<RecurrenceRelation>(
+            r#"{"base_cases":[0.0],"coefficients":[1.0,1.0]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_recurrence_solution() {
+        // f(n) = 2f(n-1) - f(n-2) with f(0) = 1 and f(1) = 2 is n + 1
+        let solution = RecurrenceSolution::new(vec![(Polynomial::new(vec![1.0, 1.0]), 1.0)]);
+        let json = serde_json::to_string(&solution).unwrap();
+        assert_eq!(json, r#"{"terms":[{"root":1.0,"coefficients":[1.0,1.0]}]}"#);
+        let parsed: RecurrenceSolution = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.get_terms(4), vec![1.0, 2.0, 3.0, 4.0]);
+        // the double root 1 of the solution found by solve keeps both of its coefficients
+        let solved = RecurrenceRelation::new(vec![1.0, 2.0], vec![2.0, -1.0]).solve();
+        let json = serde_json::to_value(&solved).unwrap();
+        assert_eq!(
+            json["terms"][0]["coefficients"].as_array().unwrap().len(),
+            2
+        );
+
+        assert!(serde_json::from_str::<RecurrenceSolution>(
+            r#"{"terms":[{"root":1.0,"coefficients":[]}]}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<RecurrenceSolution>(
+            r#"{"terms":[{"root":1.0,"multiplicity":1,"coefficients":[1.0]}]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_state_space() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let system = fibonacci.state_space(&[1.0]);
+        let json = serde_json::to_string(&system).unwrap();
+        assert_eq!(serde_json::from_str::<StateSpace>(&json).unwrap(), system);
+        assert!(serde_json::from_str::<StateSpace>(
+            r#"{"a":[[1.0,1.0]],"b":[0.0],"c":[1.0],"d":0.0,"initial_state":[0.0]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_other_types() {
+        let polynomial = RationalPolynomial::new(vec![Rational::new(-1, 2), Rational::new(1, 1)]);
+        let json = serde_json::to_string(&polynomial).unwrap();
+        assert_eq!(json, r#"{"coefficients":[[-1,2],[1,1]]}"#);
+        assert_eq!(
+            serde_json::from_str::<RationalPolynomial>(&json).unwrap(),
+            polynomial
+        );
+        assert_eq!(
+            serde_json::from_str::<RationalPolynomial>(r#"{"coefficients":[[2,-4],[3,3]]}"#)
+                .unwrap(),
+            polynomial
+        );
+        assert!(
+            serde_json::from_str::<RationalPolynomial>(r#"{"coefficients":[[1,0],[1,1]]}"#)
+                .is_err()
+        );
+
+        let error = ParseRecurrenceError::NoBaseCase;
+        assert_eq!(serde_json::to_string(&error).unwrap(), r#""NoBaseCase""#);
+
+        let exact = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0])
+            .solve_exact()
+            .unwrap();
+        let json = serde_json::to_string(&exact).unwrap();
+        assert_eq!(
+            serde_json::from_str::<crate::ExactSolution>(&json).unwrap(),
+            exact
+        );
+    }
+}
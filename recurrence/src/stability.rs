@@ -0,0 +1,245 @@
+use crate::polynomial::Polynomial;
+use crate::rational_polynomial::{Rational, RationalPolynomial};
+use crate::recurrence_relation::RecurrenceRelation;
+use num_traits::{Signed, Zero};
+
+// relative distance from the unit circle within which a computed root is taken to lie on it
+const TOLERANCE: f64 = 1e-9;
+
+/// where the characteristic roots of a recurrence lie relative to the unit circle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stability {
+    /// every root lies strictly inside the unit circle, so every solution tends to zero
+    Stable,
+    /// every root lies inside or on the unit circle and those on it are simple,
+    /// so every solution stays bounded
+    MarginallyStable,
+    /// some root lies outside the unit circle or is repeated on it, so some solution is unbounded
+    Unstable,
+}
+
+// returns x^d p(1/x), the polynomial with its coefficients reversed
+fn reciprocal(polynomial: &RationalPolynomial) -> RationalPolynomial {
+    let mut coefficients = polynomial.coefficients().to_vec();
+    coefficients.reverse();
+    RationalPolynomial::new(coefficients)
+}
+
+// returns whether every root lies strictly inside the unit circle using the schur-cohn test
+// for monic p(x) = a_0 + ... + x^n this requires |a_0| < 1, after which the roots of p are inside
+// the circle exactly when those of (p(x) - a_0 x^n p(1/x))/x are
+// returns None if the exact arithmetic overflows
+fn schur_cohn(polynomial: &RationalPolynomial) -> Option<bool> {
+    let mut p = polynomial.monic()?;
+    while p.degree() > 0 {
+        let a_0 = p.coefficients()[0];
+        if a_0.abs() >= Rational::from(1) {
+            return Some(false);
+        }
+        let reduced =
+            p.checked_sub(&RationalPolynomial::new(vec![a_0]).checked_mul(&reciprocal(&p))?)?;
+        // the constant term cancels, leaving a polynomial of one degree less
+        p = RationalPolynomial::new(reduced.coefficients()[1..].to_vec()).monic()?;
+    }
+    Some(true)
+}
+
+// returns the number of sign changes in the sturm sequence evaluated at x
+fn sign_changes(sequence: &[RationalPolynomial], x: Rational) -> Option<usize> {
+    let mut signs = Vec::new();
+    for p in sequence {
+        let y = p.evaluate(x)?;
+        if !y.is_zero() {
+            signs.push(y.is_positive());
+        }
+    }
+    Some(signs.windows(2).filter(|w| w[0] != w[1]).count())
+}
+
+// returns the number of distinct real roots in (a, b] using a sturm sequence
+fn count_real_roots(polynomial: &RationalPolynomial, a: Rational, b: Rational) -> Option<usize> {
+    let mut sequence = vec![polynomial.clone(), polynomial.derivative()?];
+    while !sequence[sequence.len() - 1].is_zero() {
+        let n = sequence.len();
+        let remainder = sequence[n - 2].div_rem(&sequence[n - 1])?.1;
+        sequence.push(-&remainder);
+    }
+    sequence.pop();
+    Some(sign_changes(&sequence, a)? - sign_changes(&sequence, b)?)
+}
+
+// returns whether every root of a self-reciprocal polynomial lies on the unit circle
+fn roots_on_unit_circle(polynomial: &RationalPolynomial) -> Option<bool> {
+    // remove the roots 1 and -1, leaving a palindromic polynomial of even degree 2m
+    let mut h = polynomial.clone();
+    for root in [1, -1] {
+        let factor = RationalPolynomial::from_integers(&[-root, 1]);
+        while h.degree() > 0 && h.evaluate(Rational::from(root))?.is_zero() {
+            h = h.div_rem(&factor)?.0;
+        }
+    }
+    if h.degree() == 0 {
+        return Some(true);
+    }
+    if h.degree() % 2 == 1 || h != reciprocal(&h) && h != -&reciprocal(&h) {
+        return Some(false);
+    }
+
+    // h(x) = x^m H(x + 1/x), where x^k + x^-k = P_k(x + 1/x) with P_0 = 2, P_1 = y and
+    // P_(k+1) = y P_k - P_(k-1), so the roots are on the circle when those of H are real in [-2, 2]
+    let m = h.degree() / 2;
+    let c = h.coefficients();
+    let y = RationalPolynomial::from_integers(&[0, 1]);
+    let mut previous = RationalPolynomial::from_integers(&[2]);
+    let mut current = y.clone();
+    let mut big_h = RationalPolynomial::new(vec![c[m]]);
+    for k in 1..=m {
+        big_h =
+            big_h.checked_add(&RationalPolynomial::new(vec![c[m + k]]).checked_mul(&current)?)?;
+        let next = y.checked_mul(&current)?.checked_sub(&previous)?;
+        previous = current;
+        current = next;
+    }
+    let square_free = big_h.div_rem(&big_h.gcd(&big_h.derivative()?)?)?.0;
+    let count = count_real_roots(&square_free, Rational::from(-2), Rational::from(2))?;
+    Some(count == square_free.degree())
+}
+
+// returns where the roots of the polynomial lie relative to the unit circle, using the roots
+// computed by complex_roots
+pub(crate) fn root_stability(polynomial: &Polynomial) -> Stability {
+    let mut stability = Stability::Stable;
+    for (root, multiplicity) in polynomial.complex_roots() {
+        let modulus = root.norm();
+        if modulus > 1.0 + TOLERANCE {
+            return Stability::Unstable;
+        }
+        if modulus >= 1.0 - TOLERANCE {
+            if multiplicity > 1 {
+                return Stability::Unstable;
+            }
+            stability = Stability::MarginallyStable;
+        }
+    }
+    stability
+}
+
+impl RecurrenceRelation {
+    /// returns where the characteristic roots lie relative to the unit circle, using the roots
+    /// computed by complex_roots
+    pub fn stability(&self) -> Stability {
+        root_stability(&self.characteristic_polynomial())
+    }
+
+    /// returns where the characteristic roots lie relative to the unit circle without computing
+    /// them, using the schur-cohn test on the coefficients in exact arithmetic
+    /// roots on the circle are the common roots of p(x) and x^n p(1/x) which are on it, and these
+    /// are located with a sturm sequence
+    /// returns None if the coefficients are not rational or the exact arithmetic overflows
+    pub fn stability_exact(&self) -> Option<Stability> {
+        let p = RationalPolynomial::from_polynomial(&self.characteristic_polynomial())?;
+        if schur_cohn(&p)? {
+            return Some(Stability::Stable);
+        }
+
+        // the roots of p on the unit circle, along with any pairs r and 1/r of roots off it
+        let common = p.gcd(&reciprocal(&p))?;
+        let rest = p.div_rem(&common)?.0;
+        let stability = if common.degree() == 0
+            || !schur_cohn(&rest)?
+            || !roots_on_unit_circle(&common)?
+            || common.gcd(&p.derivative()?)?.degree() > 0
+        {
+            Stability::Unstable
+        } else {
+            Stability::MarginallyStable
+        };
+        Some(stability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, SQRT_2};
+
+    fn relation(coefficients: Vec<f64>) -> RecurrenceRelation {
+        RecurrenceRelation::new(vec![1.0; coefficients.len()], coefficients)
+    }
+
+    #[test]
+    fn test_stability() {
+        let cases = vec![
+            // roots 1/2 and 1/3
+            (vec![5.0 / 6.0, -1.0 / 6.0], Stability::Stable),
+            // roots ±i/2
+            (vec![0.0, -0.25], Stability::Stable),
+            // the fibonacci numbers, with root 1.618
+            (vec![1.0, 1.0], Stability::Unstable),
+            // roots 1 and 1/2
+            (vec![1.5, -0.5], Stability::MarginallyStable),
+            // roots ±i and 1/2
+            (vec![0.5, -1.0, 0.5], Stability::MarginallyStable),
+            // the cube roots of unity
+            (vec![0.0, 0.0, 1.0], Stability::MarginallyStable),
+            // the double root 1
+            (vec![2.0, -1.0], Stability::Unstable),
+            // double roots ±i
+            (vec![0.0, -2.0, 0.0, -1.0], Stability::Unstable),
+            // roots 2 and 1/2, which share the reciprocal pair but lie off the circle
+            (vec![2.5, -1.0], Stability::Unstable),
+            // roots -1 and 1/3
+            (vec![-2.0 / 3.0, 1.0 / 3.0], Stability::MarginallyStable),
+        ];
+        for (coefficients, expected) in cases {
+            let relation = relation(coefficients);
+            assert_eq!(relation.stability(), expected, "{}", relation);
+            assert_eq!(relation.stability_exact(), Some(expected), "{}", relation);
+        }
+
+        // coefficients which are not rational, so only the computed roots are available
+        let cases = vec![
+            // simple conjugate roots 9e-4 apart on the unit circle
+            (vec![1.9999998, -1.0], Stability::MarginallyStable),
+            // the double root π/4 inside the circle
+            (vec![PI / 2.0, -PI * PI / 16.0], Stability::Stable),
+            // the double root -1/√2 ± i/√2 on the circle
+            (
+                vec![-2.0 * SQRT_2, -4.0, -2.0 * SQRT_2, -1.0],
+                Stability::Unstable,
+            ),
+        ];
+        for (coefficients, expected) in cases {
+            let relation = relation(coefficients);
+            assert_eq!(relation.stability(), expected, "{}", relation);
+            assert_eq!(relation.stability_exact(), None, "{}", relation);
+        }
+    }
+
+    #[test]
+    fn test_roots_on_unit_circle() {
+        // x^4 - x^3 + x^2 - x + 1 is the tenth cyclotomic polynomial
+        let polynomial = RationalPolynomial::from_integers(&[1, -1, 1, -1, 1]);
+        assert_eq!(roots_on_unit_circle(&polynomial), Some(true));
+        // x^4 - 3x^2 + 1 has roots ±φ and ±1/φ
+        let polynomial = RationalPolynomial::from_integers(&[1, 0, -3, 0, 1]);
+        assert_eq!(roots_on_unit_circle(&polynomial), Some(false));
+
+        let relation = RecurrenceRelation::new(vec![1.0], vec![std::f64::consts::PI]);
+        assert_eq!(relation.stability_exact(), None);
+        assert_eq!(relation.stability(), Stability::Unstable);
+    }
+
+    #[test]
+    fn test_stability_overflow() {
+        // the exact arithmetic on six decimal coefficients overflows i128
+        let relation = RecurrenceRelation::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            vec![0.123457, 0.234567, 0.345679, 0.456791, 0.1, 0.3],
+        );
+        assert_eq!(relation.stability_exact(), None);
+        // the coefficients sum to more than one, so there is a real root above one
+        assert_eq!(relation.stability(), Stability::Unstable);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::filter::Filter;
+use crate::polynomial::Polynomial;
+use crate::recurrence_relation::RecurrenceRelation;
+use crate::transfer_function::TransferFunction;
+use nalgebra::{DMatrix, DVector, RowDVector};
+use std::borrow::Borrow;
+
+/// a linear system in state-space form, x(n+1) = A x(n) + B u(n) and y(n) = C x(n) + D u(n),
+/// with a single input u and a single output y
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSpace {
+    a: DMatrix<f64>,
+    b: DVector<f64>,
+    c: RowDVector<f64>,
+    d: f64,
+    initial_state: DVector<f64>,
+}
+
+// returns the coefficients of det(zI - A), starting with the constant term, along with the
+// matrices M_k with adj(zI - A) = Σ M_k z^(n-k), using the faddeev-leverrier algorithm
+fn characteristic_polynomial(a: &DMatrix<f64>) -> (Vec<f64>, Vec<DMatrix<f64>>) {
+    let n = a.nrows();
+    let identity = DMatrix::<f64>::identity(n, n);
+    let mut coefficients = vec![0.0; n + 1];
+    coefficients[n] = 1.0;
+    let mut adjugate = Vec::new();
+    let mut m = DMatrix::<f64>::zeros(n, n);
+    for k in 1..=n {
+        m = a * &m + &identity * coefficients[n - k + 1];
+        coefficients[n - k] = -(a * &m).trace() / k as f64;
+        adjugate.push(m.clone());
+    }
+    (coefficients, adjugate)
+}
+
+impl StateSpace {
+    /// creates the system x(n+1) = A x(n) + B u(n), y(n) = C x(n) + D u(n) starting from x(0)
+    pub fn new(
+        a: DMatrix<f64>,
+        b: DVector<f64>,
+        c: RowDVector<f64>,
+        d: f64,
+        initial_state: DVector<f64>,
+    ) -> StateSpace {
+        let n = a.nrows();
+        if a.ncols() != n || b.len() != n || c.len() != n || initial_state.len() != n {
+            panic!("A must be square with B, C and the initial state the same size")
+        }
+        StateSpace {
+            a,
+            b,
+            c,
+            d,
+            initial_state,
+        }
+    }
+
+    pub fn a(&self) -> &DMatrix<f64> {
+        &self.a
+    }
+
+    pub fn b(&self) -> &DVector<f64> {
+        &self.b
+    }
+
+    pub fn c(&self) -> &RowDVector<f64> {
+        &self.c
+    }
+
+    pub fn d(&self) -> f64 {
+        self.d
+    }
+
+    pub fn initial_state(&self) -> &DVector<f64> {
+        &self.initial_state
+    }
+
+    /// returns the outputs for the given inputs, starting from the initial state
+    pub fn simulate<I>(&self, input: I) -> Vec<f64>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<f64>,
+    {
+        let mut state = self.initial_state.clone();
+        let mut outputs = Vec::new();
+        for u in input {
+            let u = *u.borrow();
+            outputs.push((&self.c * &state)[0] + self.d * u);
+            state = &self.a * &state + &self.b * u;
+        }
+        outputs
+    }
+
+    /// returns the transfer function C(zI - A)^-1 B + D, whose denominator is the
+    /// characteristic polynomial of A
+    pub fn transfer_function(&self) -> TransferFunction {
+        let (denominator, adjugate) = characteristic_polynomial(&self.a);
+        let n = self.a.nrows();
+        let mut numerator: Vec<f64> = denominator.iter().map(|c| c * self.d).collect();
+        for (k, m) in adjugate.iter().enumerate() {
+            numerator[n - k - 1] += (&self.c * m * &self.b)[0];
+        }
+        TransferFunction::new(Polynomial::new(numerator), Polynomial::new(denominator))
+    }
+
+    /// returns the input-output recurrence y(n) = Σ a_i y(n-i) + Σ b_j u(n-j) as a filter at rest,
+    /// with the feedback coefficients from the characteristic polynomial of A
+    pub fn filter(&self) -> Filter {
+        let transfer_function = self.transfer_function();
+        let n = self.a.nrows();
+        let denominator = transfer_function.denominator().coefficients();
+        let numerator = transfer_function.numerator().coefficients();
+        Filter::new(
+            (1..=n).map(|i| -denominator[n - i]).collect(),
+            (0..=n).map(|j| numerator[n - j]).collect(),
+        )
+    }
+
+    /// returns the recurrence relation followed by the output with no input, with the outputs
+    /// C A^n x(0) for n below the size of the state as base cases
+    pub fn recurrence_relation(&self) -> RecurrenceRelation {
+        let n = self.a.nrows();
+        let (denominator, _) = characteristic_polynomial(&self.a);
+        let mut state = self.initial_state.clone();
+        let mut base_cases = Vec::new();
+        for _ in 0..n {
+            base_cases.push((&self.c * &state)[0]);
+            state = &self.a * &state;
+        }
+        RecurrenceRelation::new(base_cases, (1..=n).map(|i| -denominator[n - i]).collect())
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns the recurrence driven by an input with the given feedforward coefficients in
+    /// state-space form, with the companion matrix of the denominator of the transfer function as A
+    /// the initial state is chosen so that with no input the output is the sequence of the relation
+    pub fn state_space(&self, feedforward: &[f64]) -> StateSpace {
+        let transfer_function = self.transfer_function(feedforward);
+        let denominator = transfer_function.denominator();
+        let n = denominator.degree();
+        let mut numerator = transfer_function.numerator().coefficients().to_vec();
+        numerator.resize(n + 1, 0.0);
+
+        // with the feedthrough D taken out, the rest of the numerator has degree below n and its
+        // coefficients make up B when the output is the last part of the state
+        let a = denominator.companion_matrix();
+        let d = numerator[n];
+        let b = DVector::from_iterator(
+            n,
+            (0..n).map(|i| numerator[i] - d * denominator.coefficients()[i]),
+        );
+        let mut c = RowDVector::zeros(n);
+        if n > 0 {
+            c[n - 1] = 1.0;
+        }
+
+        // the rows C A^i of the observability matrix give the outputs with no input
+        let mut observability = DMatrix::zeros(n, n);
+        let mut row = c.clone();
+        for i in 0..n {
+            observability.set_row(i, &row);
+            row = &row * &a;
+        }
+        let initial_state = observability
+            .lu()
+            .solve(&DVector::from_vec(self.get_terms(n)))
+            .expect("cant solve given linear system");
+
+        StateSpace::new(a, b, c, d, initial_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::assert_within;
+
+    fn assert_all_within(xs: &[f64], ys: &[f64]) {
+        assert_eq!(xs.len(), ys.len());
+        for (x, y) in xs.iter().zip(ys) {
+            assert_within(*x, *y);
+        }
+    }
+
+    #[test]
+    fn test_state_space() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let system = fibonacci.state_space(&[1.0]);
+        assert_eq!(
+            system.a(),
+            &DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 1.0])
+        );
+        assert_eq!(system.b(), &DVector::from_vec(vec![1.0, 1.0]));
+        assert_eq!(system.c(), &RowDVector::from_vec(vec![0.0, 1.0]));
+        assert_eq!(system.d(), 1.0);
+
+        // with no input the output is the sequence, and from rest it is the filter's response
+        assert_all_within(&system.simulate(vec![0.0; 8]), &fibonacci.get_terms(8));
+        assert_all_within(
+            &system.filter().impulse_response(8),
+            &fibonacci.filter(vec![1.0]).impulse_response(8),
+        );
+        assert_all_within(
+            &system.recurrence_relation().get_terms(8),
+            &fibonacci.get_terms(8),
+        );
+    }
+
+    #[test]
+    fn test_from_state_space() {
+        // two decoupled states with poles 1/2 and 2, so H(z) = (2z - 2.5)/(z^2 - 2.5z + 1)
+        let system = StateSpace::new(
+            DMatrix::from_diagonal(&DVector::from_vec(vec![0.5, 2.0])),
+            DVector::from_vec(vec![1.0, 1.0]),
+            RowDVector::from_vec(vec![1.0, 1.0]),
+            0.0,
+            DVector::from_vec(vec![1.0, 1.0]),
+        );
+        let filter = system.filter();
+        assert_all_within(filter.feedback(), &[2.5, -1.0]);
+        assert_all_within(filter.feedforward(), &[0.0, 2.0, -2.5]);
+
+        let relation = system.recurrence_relation();
+        assert_all_within(&relation.get_terms(4), &[2.0, 2.5, 4.25, 8.125]);
+
+        // the output is the free response plus the response of the filter at rest
+        let input = [1.0, -1.0, 2.0, 0.0, 3.0];
+        let mut filter = filter;
+        let expected: Vec<f64> = relation
+            .get_terms(5)
+            .iter()
+            .zip(filter.process(input))
+            .map(|(free, forced)| free + forced)
+            .collect();
+        assert_all_within(&system.simulate(input), &expected);
+    }
+}
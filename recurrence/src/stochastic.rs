@@ -0,0 +1,188 @@
+use crate::random::{Noise, Rng};
+use crate::recurrence_relation::RecurrenceRelation;
+
+/// a recurrence with a random term, f(n) = Σ a_i f(n-i) + ε(n) with ε(n) drawn independently
+/// from the noise distribution for each n past the base cases
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StochasticRecurrence {
+    relation: RecurrenceRelation,
+    noise: Noise,
+}
+
+/// the trajectories of a monte carlo simulation, with statistics of the values at each n
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonteCarlo {
+    trajectories: Vec<Vec<f64>>,
+}
+
+impl StochasticRecurrence {
+    pub fn new(relation: RecurrenceRelation, noise: Noise) -> StochasticRecurrence {
+        StochasticRecurrence { relation, noise }
+    }
+
+    pub fn relation(&self) -> &RecurrenceRelation {
+        &self.relation
+    }
+
+    pub fn noise(&self) -> &Noise {
+        &self.noise
+    }
+
+    /// returns the first n terms of one trajectory, drawing the random terms from rng
+    pub fn trajectory(&self, n: usize, rng: &mut Rng) -> Vec<f64> {
+        // the base cases take no random term
+        let noise: Vec<f64> = (0..n)
+            .map(|i| {
+                if i < self.relation.degree() {
+                    0.0
+                } else {
+                    self.noise.sample(rng)
+                }
+            })
+            .collect();
+        self.relation.drive(&[1.0], noise)
+    }
+
+    /// returns the first n terms of the given number of trajectories, all drawn from one
+    /// generator with the given seed so that the simulation can be repeated exactly
+    pub fn monte_carlo(&self, n: usize, trajectories: usize, seed: u64) -> MonteCarlo {
+        let mut rng = Rng::new(seed);
+        MonteCarlo {
+            trajectories: (0..trajectories)
+                .map(|_| self.trajectory(n, &mut rng))
+                .collect(),
+        }
+    }
+}
+
+impl MonteCarlo {
+    pub fn trajectories(&self) -> &[Vec<f64>] {
+        &self.trajectories
+    }
+
+    // returns the values of every trajectory at each n
+    fn values(&self) -> Vec<Vec<f64>> {
+        let n = self.trajectories.first().map_or(0, |t| t.len());
+        (0..n)
+            .map(|i| self.trajectories.iter().map(|t| t[i]).collect())
+            .collect()
+    }
+
+    /// returns the mean over the trajectories at each n
+    pub fn mean(&self) -> Vec<f64> {
+        self.values()
+            .iter()
+            .map(|values| values.iter().sum::<f64>() / values.len() as f64)
+            .collect()
+    }
+
+    /// returns the sample variance over the trajectories at each n
+    pub fn variance(&self) -> Vec<f64> {
+        self.values()
+            .iter()
+            .zip(self.mean())
+            .map(|(values, mean)| {
+                let squares: f64 = values.iter().map(|x| (x - mean).powi(2)).sum();
+                squares / (values.len() as f64 - 1.0).max(1.0)
+            })
+            .collect()
+    }
+
+    /// returns the q quantile over the trajectories at each n, interpolating linearly
+    /// between the sorted values
+    /// the values are sorted with total_cmp, so NaN from a diverging trajectory goes to one end
+    pub fn quantile(&self, q: f64) -> Vec<f64> {
+        self.values()
+            .into_iter()
+            .map(|mut values| {
+                values.sort_by(|a, b| a.total_cmp(b));
+                let position = q.clamp(0.0, 1.0) * (values.len() - 1) as f64;
+                let below = position.floor() as usize;
+                let above = position.ceil() as usize;
+                values[below] + (position - below as f64) * (values[above] - values[below])
+            })
+            .collect()
+    }
+
+    /// returns the band between the quantiles (1 - level)/2 and (1 + level)/2 at each n,
+    /// which holds the given fraction of the trajectories
+    pub fn band(&self, level: f64) -> (Vec<f64>, Vec<f64>) {
+        (
+            self.quantile((1.0 - level) / 2.0),
+            self.quantile((1.0 + level) / 2.0),
+        )
+    }
+
+    /// returns the statistics as csv with the columns n, mean, variance, lower and upper,
+    /// where lower and upper bound the band holding the given fraction of the trajectories
+    pub fn to_csv(&self, level: f64) -> String {
+        let mut csv = "n,mean,variance,lower,upper\n".to_string();
+        let (lower, upper) = self.band(level);
+        for (i, (mean, variance)) in self.mean().iter().zip(self.variance()).enumerate() {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                i, mean, variance, lower[i], upper[i]
+            ));
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monte_carlo() {
+        // f(n) = 0.9f(n-1) + ε(n) with f(0) = 10 and ε(n) standard normal
+        let recurrence = StochasticRecurrence::new(
+            RecurrenceRelation::new(vec![10.0], vec![0.9]),
+            Noise::Normal {
+                mean: 0.0,
+                standard_deviation: 1.0,
+            },
+        );
+        let simulation = recurrence.monte_carlo(30, 4000, 7);
+        assert_eq!(simulation, recurrence.monte_carlo(30, 4000, 7));
+        assert_ne!(simulation, recurrence.monte_carlo(30, 4000, 8));
+
+        // the mean is 10·0.9^n and the variance (1 - 0.81^n)/(1 - 0.81)
+        let mean = simulation.mean();
+        let variance = simulation.variance();
+        assert_eq!(mean[0], 10.0);
+        assert_eq!(variance[0], 0.0);
+        for n in [1, 5, 29] {
+            assert!((mean[n] - 10.0 * 0.9f64.powi(n as i32)).abs() < 0.1);
+            let expected = (1.0 - 0.81f64.powi(n as i32)) / 0.19;
+            assert!((variance[n] - expected).abs() < 0.1 * expected);
+        }
+
+        // about 90% of the values lie in the 90% band
+        let (lower, upper) = simulation.band(0.9);
+        let inside = simulation
+            .trajectories()
+            .iter()
+            .filter(|t| t[20] >= lower[20] && t[20] <= upper[20])
+            .count();
+        assert!((inside as f64 / 4000.0 - 0.9).abs() < 0.01);
+        assert_eq!(simulation.to_csv(0.9).lines().count(), 31);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let simulation = MonteCarlo {
+            trajectories: vec![vec![1.0], vec![4.0], vec![2.0], vec![3.0]],
+        };
+        assert_eq!(simulation.quantile(0.0), vec![1.0]);
+        assert_eq!(simulation.quantile(0.5), vec![2.5]);
+        assert_eq!(simulation.quantile(1.0), vec![4.0]);
+
+        let simulation = MonteCarlo {
+            trajectories: vec![vec![1.0], vec![f64::INFINITY - f64::INFINITY], vec![2.0]],
+        };
+        // the NaN sorts to one end, leaving a finite median
+        assert!(simulation.quantile(0.5)[0].is_finite());
+    }
+}
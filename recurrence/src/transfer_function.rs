@@ -0,0 +1,182 @@
+use crate::filter::Filter;
+use crate::format::FormatOptions;
+use crate::polynomial::Polynomial;
+use crate::recurrence_relation::RecurrenceRelation;
+use num_complex::Complex;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// the transfer function H(z) = Y(z)/X(z) of a filter, as a ratio of polynomials in z
+/// for y(n) = Σ a_i y(n-i) + Σ b_j x(n-j) this is (Σ b_j z^-j)/(1 - Σ a_i z^-i), multiplied
+/// through by a power of z so that both parts are polynomials
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferFunction {
+    numerator: Polynomial,
+    denominator: Polynomial,
+}
+
+// evaluates the polynomial at a complex point
+fn evaluate(polynomial: &Polynomial, z: Complex<f64>) -> Complex<f64> {
+    polynomial
+        .coefficients()
+        .iter()
+        .rev()
+        .fold(Complex::new(0.0, 0.0), |res, c| res * z + c)
+}
+
+// returns the complex roots of the polynomial, leaving out leading zero coefficients
+fn roots(polynomial: &Polynomial) -> Vec<(Complex<f64>, usize)> {
+    let coefficients = polynomial.coefficients();
+    match coefficients.iter().rposition(|c| *c != 0.0) {
+        Some(degree) if degree > 0 => {
+            Polynomial::new(coefficients[..=degree].to_vec()).complex_roots()
+        }
+        _ => Vec::new(),
+    }
+}
+
+impl TransferFunction {
+    /// creates the transfer function numerator/denominator
+    pub fn new(numerator: Polynomial, denominator: Polynomial) -> TransferFunction {
+        TransferFunction {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// returns the numerator, the polynomial whose roots are the zeros
+    pub fn numerator(&self) -> &Polynomial {
+        &self.numerator
+    }
+
+    /// returns the denominator, the polynomial whose roots are the poles
+    pub fn denominator(&self) -> &Polynomial {
+        &self.denominator
+    }
+
+    /// returns the poles with their multiplicities, the roots of the characteristic polynomial
+    /// along with a pole at zero for each feedforward coefficient past the degree
+    pub fn poles(&self) -> Vec<(Complex<f64>, usize)> {
+        roots(&self.denominator)
+    }
+
+    /// returns the zeros with their multiplicities
+    pub fn zeros(&self) -> Vec<(Complex<f64>, usize)> {
+        roots(&self.numerator)
+    }
+
+    /// returns H(z)
+    pub fn evaluate(&self, z: Complex<f64>) -> Complex<f64> {
+        evaluate(&self.numerator, z) / evaluate(&self.denominator, z)
+    }
+
+    /// returns H(e^iω) at the given number of equally spaced frequencies ω in [0, π],
+    /// as (ω, H(e^iω)) pairs
+    pub fn frequency_response(&self, points: usize) -> Vec<(f64, Complex<f64>)> {
+        (0..points)
+            .map(|i| {
+                let omega = if points > 1 {
+                    PI * i as f64 / (points - 1) as f64
+                } else {
+                    0.0
+                };
+                (omega, self.evaluate(Complex::from_polar(1.0, omega)))
+            })
+            .collect()
+    }
+
+    /// returns the frequency response as csv with the columns omega, magnitude and phase,
+    /// with the phase in radians
+    pub fn frequency_response_csv(&self, points: usize) -> String {
+        let mut csv = "omega,magnitude,phase\n".to_string();
+        for (omega, h) in self.frequency_response(points) {
+            csv.push_str(&format!("{},{},{}\n", omega, h.norm(), h.arg()));
+        }
+        csv
+    }
+}
+
+// writes the transfer function as H(z) = (numerator)/(denominator)
+impl fmt::Display for TransferFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut options = FormatOptions {
+            variable: "z".to_string(),
+            ..FormatOptions::default()
+        };
+        if let Some(precision) = f.precision() {
+            options.precision = precision;
+        }
+        write!(
+            f,
+            "H(z) = ({})/({})",
+            self.numerator.format(&options),
+            self.denominator.format(&options)
+        )
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns the transfer function of the recurrence driven by an input with the given
+    /// feedforward coefficients b_0, b_1, ..., built from the characteristic polynomial
+    pub fn transfer_function(&self, feedforward: &[f64]) -> TransferFunction {
+        let degree = self.degree();
+        let power = degree.max(feedforward.len().saturating_sub(1));
+
+        // multiplying through by z^power, b_j becomes the coefficient of z^(power - j)
+        let mut numerator = vec![0.0; power + 1];
+        for (j, b) in feedforward.iter().enumerate() {
+            numerator[power - j] = *b;
+        }
+        let mut denominator = vec![0.0; power - degree];
+        denominator.extend(self.characteristic_polynomial().coefficients());
+
+        TransferFunction::new(Polynomial::new(numerator), Polynomial::new(denominator))
+    }
+}
+
+impl Filter {
+    /// returns the transfer function of the filter
+    pub fn transfer_function(&self) -> TransferFunction {
+        let relation =
+            RecurrenceRelation::new(vec![0.0; self.feedback().len()], self.feedback().to_vec());
+        relation.transfer_function(self.feedforward())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_function() {
+        // y(n) = 0.5y(n-1) + 0.5x(n), a low pass filter
+        let low_pass = Filter::new(vec![0.5], vec![0.5]).transfer_function();
+        assert_eq!(low_pass.to_string(), "H(z) = (0.500z)/(z - 0.500)");
+        assert_eq!(low_pass.poles(), vec![(Complex::new(0.5, 0.0), 1)]);
+        assert_eq!(low_pass.zeros(), vec![(Complex::new(0.0, 0.0), 1)]);
+
+        let response = low_pass.frequency_response(3);
+        assert_eq!(response[0], (0.0, Complex::new(1.0, 0.0)));
+        assert!((response[2].1.norm() - 1.0 / 3.0).abs() < 1e-12);
+        let csv = low_pass.frequency_response_csv(3);
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(csv.lines().nth(1), Some("0,1,0"));
+
+        // f(n) = f(n-1) + f(n-2) + x(n-1) - x(n-3), with a pole at zero from the extra tap
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let transfer_function = fibonacci.transfer_function(&[0.0, 1.0, 0.0, -1.0]);
+        assert_eq!(
+            transfer_function.to_string(),
+            "H(z) = (z^2 - 1.000)/(z^3 - z^2 - z)"
+        );
+        let mut zeros: Vec<f64> = transfer_function
+            .zeros()
+            .iter()
+            .map(|(z, _)| z.re)
+            .collect();
+        zeros.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(zeros, vec![-1.0, 1.0]);
+        assert_eq!(transfer_function.poles().len(), 3);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::format::FormatOptions;
+use crate::polynomial::Polynomial;
+use crate::recurrence_relation::RecurrenceRelation;
+use crate::recurrence_solution::RecurrenceSolution;
+use std::fmt;
+
+// relative size of the imaginary part below which a pole is taken to be real
+const TOLERANCE: f64 = 1e-9;
+
+/// the z-transform F(z) = Σ f(n) z^-n of a sequence, as a ratio of polynomials in z
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZTransform {
+    numerator: Polynomial,
+    denominator: Polynomial,
+}
+
+// returns the coefficients without trailing zeros, keeping at least one
+fn trim(coefficients: &[f64]) -> Vec<f64> {
+    match coefficients.iter().rposition(|c| *c != 0.0) {
+        Some(i) => coefficients[..=i].to_vec(),
+        None => vec![0.0],
+    }
+}
+
+fn multiply(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut product = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] += x * y;
+        }
+    }
+    product
+}
+
+// returns the first n coefficients of the power series a/b, where b has a nonzero constant term
+fn series(a: &[f64], b: &[f64], n: usize) -> Vec<f64> {
+    let mut s: Vec<f64> = Vec::new();
+    for i in 0..n {
+        let mut rest = a.get(i).copied().unwrap_or(0.0);
+        for (j, coefficient) in b.iter().enumerate().take(i + 1).skip(1) {
+            rest -= coefficient * s[i - j];
+        }
+        s.push(rest / b[0]);
+    }
+    s
+}
+
+// returns the quotient of the polynomial divided by (z - r), dropping the remainder
+fn deflate(p: &[f64], r: f64) -> Vec<f64> {
+    let mut quotient = vec![0.0; p.len() - 1];
+    let mut carry = 0.0;
+    for i in (1..p.len()).rev() {
+        carry = carry * r + p[i];
+        quotient[i - 1] = carry;
+    }
+    quotient
+}
+
+// returns the coefficients of p(r + t) as a polynomial in t
+fn taylor(p: &[f64], r: f64) -> Vec<f64> {
+    // each synthetic division by (z - r) gives the next coefficient as its remainder
+    let mut p = p.to_vec();
+    let mut coefficients = Vec::new();
+    while !p.is_empty() {
+        coefficients.push(p.iter().rev().fold(0.0, |res, c| res * r + c));
+        p = if p.len() > 1 {
+            deflate(&p, r)
+        } else {
+            Vec::new()
+        };
+    }
+    coefficients
+}
+
+// returns the numerator and denominator of the generating function Σ f(n) w^n as polynomials
+// in w, where f(n) - Σ a_i f(n-i) is zero from n = k on and so the numerator has degree below k
+fn generating_function(relation: &RecurrenceRelation) -> (Vec<f64>, Vec<f64>) {
+    let mut denominator = vec![1.0];
+    denominator.extend(relation.recurrence_coefficients().iter().map(|a| -a));
+    let mut numerator = multiply(&denominator, relation.base_cases());
+    numerator.truncate(relation.degree().max(1));
+    (numerator, denominator)
+}
+
+impl ZTransform {
+    /// creates the z-transform numerator/denominator
+    pub fn new(numerator: Polynomial, denominator: Polynomial) -> ZTransform {
+        ZTransform {
+            numerator,
+            denominator,
+        }
+    }
+
+    // turns a generating function P(w)/Q(w) in w = 1/z into a ratio of polynomials in z
+    fn from_generating_function(numerator: &[f64], denominator: &[f64]) -> ZTransform {
+        let numerator = trim(numerator);
+        let denominator = trim(denominator);
+        let power = denominator.len().max(numerator.len()) - 1;
+        // multiplying through by z^power, the coefficient of w^i becomes that of z^(power - i)
+        let reverse = |coefficients: &[f64]| {
+            let mut reversed = vec![0.0; power + 1];
+            for (i, c) in coefficients.iter().enumerate() {
+                reversed[power - i] = *c;
+            }
+            Polynomial::new(trim(&reversed))
+        };
+        ZTransform::new(reverse(&numerator), reverse(&denominator))
+    }
+
+    pub fn numerator(&self) -> &Polynomial {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &Polynomial {
+        &self.denominator
+    }
+
+    /// returns the recurrence relation whose sequence has this z-transform, with the first terms
+    /// found by expanding F(z) in powers of 1/z and the coefficients read off the denominator
+    /// returns None if F(z) does not vanish as z grows, in which case the sequence would have
+    /// terms before n = 0
+    pub fn recurrence_relation(&self) -> Option<RecurrenceRelation> {
+        let numerator = trim(self.numerator.coefficients());
+        let denominator = trim(self.denominator.coefficients());
+        if denominator == [0.0] || numerator.len() > denominator.len() {
+            return None;
+        }
+        // back in terms of w = 1/z, where the denominator has a nonzero constant term
+        let power = denominator.len() - 1;
+        let mut p: Vec<f64> = numerator.iter().rev().copied().collect();
+        p.splice(0..0, vec![0.0; power + 1 - numerator.len()]);
+        let q: Vec<f64> = denominator.iter().rev().copied().collect();
+        let p = trim(&p);
+
+        // the recurrence holds once n is past the degree of the numerator
+        let degree = (q.len() - 1).max(p.len());
+        let base_cases = series(&p, &q, degree);
+        let coefficients = (1..=degree)
+            .map(|i| -q.get(i).copied().unwrap_or(0.0) / q[0])
+            .collect();
+        Some(RecurrenceRelation::new(base_cases, coefficients))
+    }
+
+    /// returns the sequence with this z-transform in closed form, found by expanding F(z)/z
+    /// in partial fractions and inverting each term, where z/(z - r)^j is the transform of
+    /// C(n, j-1) r^(n-j+1)
+    /// returns None if F(z) does not vanish as z grows, has complex poles, or has a pole at zero,
+    /// since the solution has no terms for these
+    pub fn inverse(&self) -> Option<RecurrenceSolution> {
+        let mut numerator = trim(self.numerator.coefficients());
+        let denominator = trim(self.denominator.coefficients());
+        if denominator == [0.0] || numerator.len() > denominator.len() {
+            return None;
+        }
+        // F(z)/z, cancelling any common power of z
+        let mut denominator = multiply(&[0.0, 1.0], &denominator);
+        while numerator.len() > 1 && numerator[0] == 0.0 && denominator[0] == 0.0 {
+            numerator.remove(0);
+            denominator.remove(0);
+        }
+
+        let mut terms = Vec::new();
+        for (root, multiplicity) in Polynomial::new(denominator.clone()).complex_roots() {
+            if root.im.abs() > TOLERANCE * root.norm() {
+                return None;
+            }
+            let r = root.re;
+
+            // the coefficient c_j of 1/(z - r)^j is the coefficient of t^(m-j) in the expansion
+            // of (z - r)^m F(z)/z about z = r + t
+            let mut rest = denominator.clone();
+            for _ in 0..multiplicity {
+                rest = deflate(&rest, r);
+            }
+            let expansion = series(&taylor(&numerator, r), &taylor(&rest, r), multiplicity);
+
+            if r == 0.0 {
+                // 1/z is the transform of the unit impulse, which is 0^n, and higher powers
+                // of 1/z are delayed impulses
+                if expansion[..multiplicity - 1].iter().any(|c| *c != 0.0) {
+                    return None;
+                }
+                terms.push((Polynomial::new(vec![expansion[multiplicity - 1]]), 0.0));
+                continue;
+            }
+
+            let mut polynomial = vec![0.0; multiplicity];
+            // C(n, j-1) as a polynomial in n
+            let mut binomial = vec![1.0];
+            for j in 1..=multiplicity {
+                let c = expansion[multiplicity - j] * r.powi(1 - j as i32);
+                for (i, b) in binomial.iter().enumerate() {
+                    polynomial[i] += c * b;
+                }
+                binomial = multiply(&binomial, &[-(j as f64 - 1.0) / j as f64, 1.0 / j as f64]);
+            }
+            terms.push((Polynomial::new(polynomial), r));
+        }
+        Some(RecurrenceSolution::new(terms))
+    }
+}
+
+// writes the z-transform as F(z) = (numerator)/(denominator)
+impl fmt::Display for ZTransform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut options = FormatOptions {
+            variable: "z".to_string(),
+            ..FormatOptions::default()
+        };
+        if let Some(precision) = f.precision() {
+            options.precision = precision;
+        }
+        write!(
+            f,
+            "F(z) = ({})/({})",
+            self.numerator.format(&options),
+            self.denominator.format(&options)
+        )
+    }
+}
+
+impl RecurrenceRelation {
+    /// returns the z-transform of the sequence, which has the characteristic polynomial
+    /// as its denominator
+    pub fn z_transform(&self) -> ZTransform {
+        let (numerator, denominator) = generating_function(self);
+        ZTransform::from_generating_function(&numerator, &denominator)
+    }
+
+    /// returns the z-transform of the sequence with f(n) = Σ a_i f(n-i) + g(n) from n = k on,
+    /// where the forcing g is the sequence of another recurrence relation
+    pub fn z_transform_forced(&self, forcing: &RecurrenceRelation) -> ZTransform {
+        // F(w)Q(w) is the numerator P(w) for the first k terms and G(w) after them,
+        // so with G = P_g/Q_g, F = ((P - G mod w^k)Q_g + P_g)/(Q Q_g)
+        let (p, q) = generating_function(self);
+        let (p_g, q_g) = generating_function(forcing);
+        let mut start = p;
+        for (i, g) in forcing.get_terms(self.degree()).iter().enumerate() {
+            start[i] -= g;
+        }
+        let mut numerator = multiply(&start, &q_g);
+        for (i, c) in p_g.iter().enumerate() {
+            numerator[i] += c;
+        }
+        ZTransform::from_generating_function(&numerator, &multiply(&q, &q_g))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::assert_within;
+
+    fn assert_terms(solution: &RecurrenceSolution, expected: &[f64]) {
+        let terms = solution.get_terms(expected.len() as u32);
+        for (term, expected) in terms.iter().zip(expected) {
+            assert_within(*term, *expected);
+        }
+    }
+
+    #[test]
+    fn test_z_transform() {
+        let fibonacci = RecurrenceRelation::new(vec![0.0, 1.0], vec![1.0, 1.0]);
+        let transform = fibonacci.z_transform();
+        assert_eq!(transform.to_string(), "F(z) = (z)/(z^2 - z - 1.000)");
+        assert_eq!(transform.recurrence_relation(), Some(fibonacci.clone()));
+        assert_terms(&transform.inverse().unwrap(), &fibonacci.get_terms(10));
+
+        // f(n) = 2f(n-1) + 1 with f(0) = 0, which is 2^n - 1
+        let relation = RecurrenceRelation::new(vec![0.0], vec![2.0]);
+        let ones = RecurrenceRelation::new(vec![1.0], vec![1.0]);
+        let transform = relation.z_transform_forced(&ones);
+        assert_eq!(transform.to_string(), "F(z) = (z)/(z^2 - 3.000z + 2.000)");
+        assert_terms(&transform.inverse().unwrap(), &[0.0, 1.0, 3.0, 7.0, 15.0]);
+        assert_eq!(
+            transform.recurrence_relation().unwrap().get_terms(5),
+            vec![0.0, 1.0, 3.0, 7.0, 15.0]
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        // z^2/(z - 1)^2 is the transform of n + 1
+        let transform = ZTransform::new(
+            Polynomial::new(vec![0.0, 0.0, 1.0]),
+            Polynomial::new(vec![1.0, -2.0, 1.0]),
+        );
+        assert_terms(&transform.inverse().unwrap(), &[1.0, 2.0, 3.0, 4.0]);
+
+        // (z + 2)/(z - 1) is 1, 3, 3, 3, ..., with a unit impulse at n = 0
+        let transform = ZTransform::new(
+            Polynomial::new(vec![2.0, 1.0]),
+            Polynomial::new(vec![-1.0, 1.0]),
+        );
+        assert_terms(&transform.inverse().unwrap(), &[1.0, 3.0, 3.0, 3.0]);
+        assert_eq!(
+            transform.recurrence_relation().unwrap().get_terms(4),
+            vec![1.0, 3.0, 3.0, 3.0]
+        );
+
+        // 1/(z^2 + 1) has complex poles, and 1/z^2 is a delayed impulse
+        let transform = ZTransform::new(
+            Polynomial::new(vec![1.0]),
+            Polynomial::new(vec![1.0, 0.0, 1.0]),
+        );
+        assert!(transform.inverse().is_none());
+        let transform = ZTransform::new(
+            Polynomial::new(vec![1.0]),
+            Polynomial::new(vec![0.0, 0.0, 1.0]),
+        );
+        assert!(transform.inverse().is_none());
+    }
+}